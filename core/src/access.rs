@@ -0,0 +1,145 @@
+use crate::Field;
+
+/// A field in a field list that is projected to a shared reference
+///
+/// Projecting a `&mut Parent` to a field list normally gives a unique
+/// reference to every field, and panics if any of them overlap. Shared fields
+/// are given as shared references instead, so they may overlap other shared
+/// fields, but not any unique fields.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Field, ProjectAll, Shared, Unique};
+///
+/// #[derive(Field)]
+/// struct Header {
+///     len:  usize,
+///     kind: u8,
+/// }
+///
+/// #[derive(Field)]
+/// struct Packet {
+///     header: Header,
+///     body:   Vec<u8>,
+/// }
+///
+/// fn main() {
+///     let fields = Packet::fields();
+///     let mut packet = Packet {
+///         header: Header {
+///             len:  3,
+///             kind: 1,
+///         },
+///         body:   Vec::new(),
+///     };
+///
+///     let typsy::hlist_pat!(header, len, body) =
+///         (&mut packet).project_all(typsy::hlist!(
+///             Shared(fields.header),
+///             Shared(fields.header.len),
+///             Unique(fields.body),
+///         ));
+///
+///     body.resize(*len, header.kind);
+///     assert_eq!(packet.body, [1, 1, 1]);
+/// }
+/// # }
+/// ```
+///
+/// Shared fields may be in more than one [`Partial`](crate::Partial) at the
+/// same time, so these can only be sent to other threads if the field is
+/// `Sync`
+///
+/// ```compile_fail
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Disjoint, Field, Partial, Shared};
+/// use std::cell::Cell;
+///
+/// #[derive(Field)]
+/// struct Header {
+///     count: Cell<u32>,
+/// }
+///
+/// #[derive(Field)]
+/// struct Packet {
+///     header: Header,
+/// }
+///
+/// fn main() {
+///     let fields = Packet::fields();
+///     let mut packet = Packet {
+///         header: Header {
+///             count: Cell::new(0),
+///         },
+///     };
+///
+///     let list = typsy::hlist!(
+///         Shared(fields.header),
+///         Shared(fields.header.count),
+///     );
+///     let partial = Partial::new(&mut packet, Disjoint::new(list));
+///     let (header, count) =
+///         partial.split(typsy::hlist!(Shared(fields.header)));
+///
+///     std::thread::scope(|scope| {
+///         scope.spawn(move || {
+///             let typsy::hlist_pat!(header) = header.project_all();
+///             header.count.set(1);
+///         });
+///
+///         let typsy::hlist_pat!(count) = count.project_all();
+///         count.set(2);
+///     });
+/// }
+/// # }
+/// ```
+#[repr(transparent)]
+#[derive(Copy, Clone)]
+pub struct Shared<F: Field>(pub F);
+
+/// A field in a field list that is projected to a unique reference
+///
+/// This is the same as using the field on its own, but makes it clear which
+/// fields are mutated in a list with [`Shared`] fields.
+#[repr(transparent)]
+#[derive(Copy, Clone)]
+pub struct Unique<F: Field>(pub F);
+
+impl<F: Field> Shared<F> {
+    /// Project to `field` as a shared reference
+    #[inline]
+    pub fn new(field: F) -> Self {
+        Self(field)
+    }
+}
+
+impl<F: Field> Unique<F> {
+    /// Project to `field` as a unique reference
+    #[inline]
+    pub fn new(field: F) -> Self {
+        Self(field)
+    }
+}
+
+unsafe impl<F: Field> Field for Unique<F> {
+    type Parent = F::Parent;
+    type Type = F::Type;
+
+    #[inline]
+    unsafe fn project_raw(
+        &self,
+        ptr: *const Self::Parent,
+    ) -> *const Self::Type {
+        F::project_raw(&self.0, ptr)
+    }
+
+    #[inline]
+    unsafe fn project_raw_mut(
+        &self,
+        ptr: *mut Self::Parent,
+    ) -> *mut Self::Type {
+        F::project_raw_mut(&self.0, ptr)
+    }
+}
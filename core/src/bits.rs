@@ -0,0 +1,375 @@
+//! Bit-field projections within integer fields
+
+use crate::{
+    project::{from_mut::PtrToRefMut, PtrToRef},
+    type_list::Access,
+    Field,
+};
+
+use core::{marker::PhantomData, ops::Range};
+
+/// An integer type that can hold bit-fields
+///
+/// Bit-fields only hold bits, so a bit-field of a signed integer is read
+/// without extending its sign.
+pub trait BitInt: Copy {
+    /// The number of bits in the integer
+    const BITS: u32;
+
+    /// Zero-extend the integer to a `u128`
+    fn to_bits(self) -> u128;
+
+    /// Truncate a `u128` to this integer type
+    fn from_bits(bits: u128) -> Self;
+}
+
+macro_rules! bit_int {
+    ($($ty:ty => $unsigned:ty),* $(,)?) => {$(
+        impl BitInt for $ty {
+            const BITS: u32 = (core::mem::size_of::<$ty>() * 8) as u32;
+
+            #[inline]
+            fn to_bits(self) -> u128 {
+                self as $unsigned as u128
+            }
+
+            #[inline]
+            fn from_bits(bits: u128) -> Self {
+                bits as $ty
+            }
+        }
+    )*};
+}
+
+bit_int! {
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128,
+    usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128,
+    isize => usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct BitSpan {
+    start: u32,
+    len:   u32,
+}
+
+impl BitSpan {
+    fn mask(self) -> u128 {
+        if self.len == 128 {
+            !0
+        } else {
+            ((1 << self.len) - 1) << self.start
+        }
+    }
+
+    fn extract<T: BitInt>(self, value: T) -> T {
+        T::from_bits((value.to_bits() & self.mask()) >> self.start)
+    }
+
+    fn insert<T: BitInt>(self, value: T, bits: T) -> T {
+        let mask = self.mask();
+        let bits = (bits.to_bits() << self.start) & mask;
+        T::from_bits((value.to_bits() & !mask) | bits)
+    }
+}
+
+/// A range of bits inside of an integer field
+///
+/// Bits are numbered from the least significant bit of the field, so
+/// `bits(4..8)` of a `u16` covers the second nibble, regardless of the
+/// endianness of the target.
+///
+/// Bit-fields can be put in a list of fields, where they only overlap other
+/// bit-fields of the same integer field if their bits overlap. Projecting the
+/// list gives a [`BitRef`] or [`BitMut`] for each bit-field.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Field, ProjectAll};
+///
+/// #[derive(Field)]
+/// struct Frame {
+///     header:  u16,
+///     payload: [u8; 4],
+/// }
+///
+/// fn main() {
+///     let fields = Frame::fields();
+///     let mut frame = Frame {
+///         header:  0,
+///         payload: [0; 4],
+///     };
+///
+///     let typsy::hlist_pat!(mut version, mut kind, payload) =
+///         (&mut frame).project_all(typsy::hlist!(
+///             fields.header.bits(0..4),
+///             fields.header.bits(4..8),
+///             fields.payload,
+///         ));
+///
+///     version.set(1);
+///     kind.set(0xa);
+///     payload[0] = version.get() as u8;
+///
+///     assert_eq!(frame.header, 0xa1);
+///     assert_eq!(frame.payload, [1, 0, 0, 0]);
+/// }
+/// # }
+/// ```
+///
+/// Generated from [`Field::bits`]
+pub struct Bits<F> {
+    field: F,
+    span:  BitSpan,
+}
+
+impl<F: Copy> Copy for Bits<F> {
+}
+impl<F: Clone> Clone for Bits<F> {
+    fn clone(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            span:  self.span,
+        }
+    }
+}
+
+impl<F: Field> Bits<F>
+where
+    F::Type: BitInt,
+{
+    /// Create a new bit-field from the given bits of an integer field
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty, or if it doesn't fit inside of `F::Type`
+    pub fn new(field: F, bits: Range<u32>) -> Self {
+        assert!(
+            bits.start < bits.end && bits.end <= F::Type::BITS,
+            "invalid bit range {:?} for a {}-bit field",
+            bits,
+            F::Type::BITS
+        );
+
+        Self {
+            field,
+            span: BitSpan {
+                start: bits.start,
+                len:   bits.end - bits.start,
+            },
+        }
+    }
+
+    /// Get the underlying integer field
+    pub fn field(&self) -> &F {
+        &self.field
+    }
+
+    /// The bits of `F::Type` covered by this bit-field
+    pub fn bits(&self) -> Range<u32> {
+        self.span.start..self.span.start + self.span.len
+    }
+
+    /// The mask of this bit-field inside of `F::Type`
+    pub fn mask(&self) -> F::Type {
+        F::Type::from_bits(self.span.mask())
+    }
+
+    /// Checks if two bit-fields of the same `Parent` could alias each other
+    ///
+    /// Bit-fields inside of the same integer field are compared bit by bit,
+    /// otherwise they are compared by the bytes of their integer fields. This
+    /// is the same check that is used for lists of fields.
+    pub fn is_overlapping<G: Field<Parent = F::Parent>>(
+        &self,
+        other: &Bits<G>,
+    ) -> bool
+    where
+        G::Type: BitInt,
+    {
+        crate::project::is_overlapping_entry(self, other)
+    }
+
+    /// Read the bit-field out of the parent
+    pub fn get(&self, parent: &F::Parent) -> F::Type {
+        unsafe { self.span.extract(*self.field.project_raw(parent)) }
+    }
+
+    /// Write the bit-field into the parent, any bits of `value` that don't
+    /// fit in the bit-field are ignored
+    pub fn set(&self, parent: &mut F::Parent, value: F::Type) {
+        unsafe {
+            let field = &mut *self.field.project_raw_mut(parent);
+            *field = self.span.insert(*field, value);
+        }
+    }
+
+    /// Project a reference to the parent to a read-only bit-field proxy
+    pub fn project<'a>(&self, parent: &'a F::Parent) -> BitRef<'a, F::Type> {
+        BitRef {
+            value: unsafe { &*self.field.project_raw(parent) },
+            span:  self.span,
+        }
+    }
+
+    /// Project a mutable reference to the parent to a bit-field proxy
+    pub fn project_mut<'a>(
+        &self,
+        parent: &'a mut F::Parent,
+    ) -> BitMut<'a, F::Type> {
+        BitMut {
+            ptr:  unsafe { self.field.project_raw_mut(parent) },
+            span: self.span,
+            mark: PhantomData,
+        }
+    }
+
+    /// Project a raw pointer to the parent to a bit-field proxy which uses
+    /// volatile reads and writes
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must point to a valid allocation of `Parent`
+    /// * `ptr` must be valid for volatile reads and writes for as long as the
+    ///   proxy is used
+    pub unsafe fn project_volatile(
+        &self,
+        ptr: *mut F::Parent,
+    ) -> BitVolatile<F::Type> {
+        BitVolatile {
+            ptr:  self.field.project_raw_mut(ptr),
+            span: self.span,
+        }
+    }
+}
+
+/// A read-only proxy to a bit-field
+pub struct BitRef<'a, T> {
+    value: &'a T,
+    span:  BitSpan,
+}
+
+impl<T: BitInt> BitRef<'_, T> {
+    /// Read the bit-field
+    pub fn get(&self) -> T {
+        self.span.extract(*self.value)
+    }
+}
+
+/// A mutable proxy to a bit-field
+///
+/// Other `BitMut`s to the same integer field may exist at the same time, as
+/// long as their bits don't overlap, so a `BitMut` can't be sent to another
+/// thread.
+pub struct BitMut<'a, T> {
+    ptr:  *mut T,
+    span: BitSpan,
+    mark: PhantomData<&'a mut T>,
+}
+
+impl<T: BitInt> BitMut<'_, T> {
+    /// Read the bit-field
+    pub fn get(&self) -> T {
+        // Safety: `ptr` is valid for `'a`, and is only ever accessed through
+        // proxies on this thread, none of which hold a reference to it
+        unsafe { self.span.extract(*self.ptr) }
+    }
+
+    /// Write the bit-field, any bits of `value` that don't fit in the
+    /// bit-field are ignored
+    pub fn set(&mut self, value: T) {
+        // Safety: see `get`, the other bits are written back unchanged
+        unsafe { *self.ptr = self.span.insert(*self.ptr, value) }
+    }
+}
+
+/// A proxy to a bit-field that only uses volatile reads and writes
+///
+/// Writes are a volatile read of the whole integer field, followed by a
+/// volatile write of the whole integer field.
+pub struct BitVolatile<T> {
+    ptr:  *mut T,
+    span: BitSpan,
+}
+
+impl<T: BitInt> BitVolatile<T> {
+    /// Read the bit-field
+    pub fn get(&self) -> T {
+        unsafe { self.span.extract(self.ptr.read_volatile()) }
+    }
+
+    /// Write the bit-field, any bits of `value` that don't fit in the
+    /// bit-field are ignored
+    pub fn set(&mut self, value: T) {
+        unsafe {
+            let bits = self.span.insert(self.ptr.read_volatile(), value);
+            self.ptr.write_volatile(bits)
+        }
+    }
+}
+
+/// A bit-field that was projected from a raw pointer to its parent, used to
+/// project lists of fields that hold [`Bits`]
+pub struct RawBits<P> {
+    ptr:  P,
+    span: BitSpan,
+}
+
+impl<F: Field> Bits<F> {
+    pub(crate) unsafe fn project_raw(
+        &self,
+        ptr: *const F::Parent,
+    ) -> RawBits<*const F::Type> {
+        RawBits {
+            ptr:  self.field.project_raw(ptr),
+            span: self.span,
+        }
+    }
+
+    pub(crate) unsafe fn project_raw_mut(
+        &self,
+        ptr: *mut F::Parent,
+    ) -> RawBits<*mut F::Type> {
+        RawBits {
+            ptr:  self.field.project_raw_mut(ptr),
+            span: self.span,
+        }
+    }
+}
+
+impl<F: Field> Access for Bits<F> {
+    const UNIQUE: bool = true;
+
+    fn range(&self) -> Range<usize> {
+        self.field.range()
+    }
+
+    fn bit_range(&self) -> Option<Range<u32>> {
+        Some(self.span.start..self.span.start + self.span.len)
+    }
+}
+
+typsy::call! {
+    fn['a, T: 'a](
+        &mut self: PtrToRef<'a>,
+        bits: RawBits<*const T>
+    ) -> BitRef<'a, T> {
+        BitRef {
+            value: unsafe { &*bits.ptr },
+            span:  bits.span,
+        }
+    }
+
+    fn['a, T: 'a](
+        &mut self: PtrToRefMut<'a>,
+        bits: RawBits<*mut T>
+    ) -> BitMut<'a, T> {
+        BitMut {
+            ptr:  bits.ptr,
+            span: bits.span,
+            mark: PhantomData,
+        }
+    }
+}
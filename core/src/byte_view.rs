@@ -0,0 +1,202 @@
+use crate::{Pod, RelPtr};
+
+use core::{fmt, marker::PhantomData, mem};
+
+/// A byte buffer that has been checked to hold a `P`
+///
+/// Once created, a `ByteView` can project to any `Field` of `P` without
+/// copying the bytes out of the buffer.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{ByteView, Field, ProjectTo};
+///
+/// #[derive(Clone, Copy, Field)]
+/// #[field(pod)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// #[repr(align(4))]
+/// struct Buffer([u8; 8]);
+///
+/// fn main() {
+///     let mut buffer = Buffer([0; 8]);
+///     buffer.0[4..].copy_from_slice(&3_u32.to_ne_bytes());
+///
+///     let view = ByteView::<Header>::new(&buffer.0).unwrap();
+///     let len: &u32 = view.project_to(Header::fields().len);
+///
+///     assert_eq!(*len, 3);
+/// }
+/// # }
+/// ```
+pub struct ByteView<'a, P> {
+    buffer: &'a [u8],
+    start:  usize,
+    _mark:  PhantomData<&'a P>,
+}
+
+impl<P> Copy for ByteView<'_, P> {
+}
+impl<P> Clone for ByteView<'_, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// The error returned when a buffer can't be viewed as a given type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteViewError {
+    /// The buffer is smaller than the type
+    TooShort {
+        /// The size of the type
+        needed: usize,
+        /// The size of the buffer
+        len:    usize,
+    },
+    /// The buffer isn't aligned for the type
+    Misaligned {
+        /// The alignment of the type
+        align: usize,
+    },
+    /// A position or relative pointer is outside of the buffer
+    OutOfBounds,
+}
+
+impl fmt::Display for ByteViewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ByteViewError::TooShort {
+                needed,
+                len,
+            } => {
+                write!(f, "buffer of {} bytes is too short for {}", len, needed)
+            },
+            ByteViewError::Misaligned {
+                align,
+            } => write!(f, "buffer is not aligned to {} bytes", align),
+            ByteViewError::OutOfBounds => f.write_str("out of bounds of buffer"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ByteViewError {
+}
+
+impl<'a, P: Pod> ByteView<'a, P> {
+    /// Check that `bytes` is large enough and aligned enough to hold a `P`
+    ///
+    /// Any trailing bytes after the first `size_of::<P>()` are kept, but are
+    /// not reachable through projections
+    pub fn new(bytes: &'a [u8]) -> Result<Self, ByteViewError> {
+        Self::new_at(bytes, 0)
+    }
+
+    /// Check that `buffer` holds a `P` starting at the byte `start`
+    ///
+    /// The whole buffer is kept, so that relative pointers inside of the `P`
+    /// can be followed to anywhere in the buffer with [`ByteView::follow`]
+    pub fn new_at(
+        buffer: &'a [u8],
+        start: usize,
+    ) -> Result<Self, ByteViewError> {
+        let bytes = buffer.get(start..).ok_or(ByteViewError::OutOfBounds)?;
+
+        if bytes.len() < mem::size_of::<P>() {
+            return Err(ByteViewError::TooShort {
+                needed: mem::size_of::<P>(),
+                len:    bytes.len(),
+            })
+        }
+
+        if !(bytes.as_ptr() as usize).is_multiple_of(mem::align_of::<P>()) {
+            return Err(ByteViewError::Misaligned {
+                align: mem::align_of::<P>(),
+            })
+        }
+
+        Ok(Self {
+            buffer,
+            start,
+            _mark: PhantomData,
+        })
+    }
+
+    /// The underlying buffer, starting at the `P`
+    pub fn bytes(&self) -> &'a [u8] {
+        &self.buffer[self.start..]
+    }
+
+    /// Follow a relative pointer that is stored inside of this buffer, this
+    /// returns `None` if the pointer is null
+    ///
+    /// ```rust
+    /// #![feature(raw_ref_op)]
+    /// # mod main {
+    /// use gfp_core::{ByteView, Field, ProjectTo, RelPtr};
+    ///
+    /// #[derive(Clone, Copy, Field)]
+    /// #[field(pod)]
+    /// #[repr(C)]
+    /// struct Node {
+    ///     value: u32,
+    ///     next:  RelPtr<Node>,
+    /// }
+    ///
+    /// #[repr(align(8))]
+    /// struct Buffer([u8; 32]);
+    ///
+    /// fn main() {
+    ///     let mut buffer = Buffer([0; 32]);
+    ///     buffer.0[..4].copy_from_slice(&1_u32.to_ne_bytes());
+    ///     // the `next` field is at byte 8, and the next node is at byte 16
+    ///     buffer.0[8..16].copy_from_slice(&8_isize.to_ne_bytes());
+    ///     buffer.0[16..20].copy_from_slice(&2_u32.to_ne_bytes());
+    ///
+    ///     let node = Node::fields();
+    ///     let first = ByteView::<Node>::new(&buffer.0).unwrap();
+    ///     let second = first.follow(first.project_to(node.next)).unwrap();
+    ///     let second = second.unwrap();
+    ///
+    ///     assert_eq!(*second.project_to(node.value), 2);
+    ///     assert!(second.follow(second.project_to(node.next)).unwrap().is_none());
+    /// }
+    /// # }
+    /// ```
+    pub fn follow<T: Pod>(
+        &self,
+        ptr: &'a RelPtr<T>,
+    ) -> Result<Option<ByteView<'a, T>>, ByteViewError> {
+        let base = self.buffer.as_ptr() as usize;
+        let position = (ptr as *const RelPtr<T> as usize).wrapping_sub(base);
+
+        if position >= self.buffer.len() {
+            return Err(ByteViewError::OutOfBounds)
+        }
+
+        if ptr.is_null() {
+            return Ok(None)
+        }
+
+        let target = (position as isize)
+            .checked_add(ptr.offset())
+            .filter(|&target| target >= 0)
+            .ok_or(ByteViewError::OutOfBounds)?;
+
+        ByteView::new_at(self.buffer, target as usize).map(Some)
+    }
+
+    /// Get the whole `P` out of the buffer
+    pub fn get(&self) -> &'a P {
+        unsafe { &*self.as_ptr() }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const P {
+        self.bytes().as_ptr().cast()
+    }
+}
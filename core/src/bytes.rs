@@ -0,0 +1,155 @@
+//! Byte level access to the fields of a `Parent`
+//!
+//! The fields are read and written one by one, so the padding bytes between
+//! fields are never exposed.
+
+use crate::{AsBytes, Field, Pod};
+
+use core::{mem, ptr};
+use typsy::cmp::Any;
+
+/// Copies each field in a list, used by [`copy_fields`]
+pub struct CopyField<P> {
+    dst: *mut P,
+    src: *const P,
+}
+
+/// Clones each field in a list, used by [`clone_fields`]
+pub struct CloneField<P> {
+    dst: *mut P,
+    src: *const P,
+}
+
+/// Sums the sizes of the fields in a list, used by [`fields_len`]
+pub struct FieldsLen(*mut usize);
+
+/// Writes the bytes of each field in a list, used by [`write_fields`]
+pub struct WriteBytes<P> {
+    src:   *const P,
+    bytes: *mut u8,
+}
+
+/// Reads the bytes of each field in a list, used by [`read_fields`]
+pub struct ReadBytes<P> {
+    dst:   *mut P,
+    bytes: *const u8,
+}
+
+typsy::call! {
+    fn[F: Field](&mut self: CopyField<F::Parent>, field: F) -> bool
+    where(F::Type: Copy) {
+        unsafe { *field.project_raw_mut(self.dst) = *field.project_raw(self.src) }
+        false
+    }
+
+    fn[F: Field](&mut self: CloneField<F::Parent>, field: F) -> bool
+    where(F::Type: Clone) {
+        unsafe {
+            let dst = &mut *field.project_raw_mut(self.dst);
+            dst.clone_from(&*field.project_raw(self.src))
+        }
+        false
+    }
+
+    fn[F: Field](&mut self: FieldsLen, _field: F) -> bool {
+        unsafe { *self.0 += mem::size_of::<F::Type>() }
+        false
+    }
+
+    fn[F: Field](&mut self: WriteBytes<F::Parent>, field: F) -> bool
+    where(F::Type: AsBytes) {
+        unsafe {
+            let src = field.bytes(&*self.src);
+            ptr::copy_nonoverlapping(src.as_ptr(), self.bytes, src.len());
+            self.bytes = self.bytes.add(src.len());
+        }
+
+        false
+    }
+
+    fn[F: Field](&mut self: ReadBytes<F::Parent>, field: F) -> bool
+    where(F::Type: Pod + AsBytes) {
+        unsafe {
+            let dst = field.bytes_mut(&mut *self.dst);
+            ptr::copy_nonoverlapping(self.bytes, dst.as_mut_ptr(), dst.len());
+            self.bytes = self.bytes.add(dst.len());
+        }
+
+        false
+    }
+}
+
+/// Copy every field in `fields` from `src` to `dst`
+pub fn copy_fields<P, L>(dst: &mut P, src: &P, fields: L)
+where
+    L: for<'b> Any<'b, CopyField<P>>,
+{
+    fields.any(CopyField {
+        dst,
+        src,
+    });
+}
+
+/// Clone every field in `fields` from `src` into `dst`, see
+/// [`Field::clone_field_from`]
+pub fn clone_fields<P, L>(dst: &mut P, src: &P, fields: L)
+where
+    L: for<'b> Any<'b, CloneField<P>>,
+{
+    fields.any(CloneField {
+        dst,
+        src,
+    });
+}
+
+/// The total size of every field in `fields`, without any padding
+pub fn fields_len<L>(fields: L) -> usize
+where
+    L: for<'b> Any<'b, FieldsLen>,
+{
+    let mut len = 0;
+    fields.any(FieldsLen(&mut len));
+    len
+}
+
+/// Write the bytes of every field in `fields` back to back into `bytes`,
+/// without any padding, and return the number of bytes written
+///
+/// # Panics
+///
+/// If `bytes` is shorter than [`fields_len`]
+pub fn write_fields<P, L>(src: &P, fields: L, bytes: &mut [u8]) -> usize
+where
+    L: Copy + for<'b> Any<'b, FieldsLen> + for<'b> Any<'b, WriteBytes<P>>,
+{
+    let len = fields_len(fields);
+    assert!(bytes.len() >= len, "buffer is too short for the fields");
+
+    fields.any(WriteBytes {
+        src,
+        bytes: bytes.as_mut_ptr(),
+    });
+
+    len
+}
+
+/// Read every field in `fields` back to back out of `bytes`, in the format
+/// written by [`write_fields`], and return the number of bytes read
+///
+/// # Panics
+///
+/// If `bytes` is shorter than [`fields_len`]
+pub fn read_fields<P, L>(dst: &mut P, fields: L, bytes: &[u8]) -> usize
+where
+    L: Copy + for<'b> Any<'b, FieldsLen> + for<'b> Any<'b, ReadBytes<P>>,
+{
+    let len = fields_len(fields);
+    assert!(bytes.len() >= len, "buffer is too short for the fields");
+
+    fields.any(ReadBytes {
+        dst,
+        bytes: bytes.as_ptr(),
+    });
+
+    len
+}
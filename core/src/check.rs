@@ -0,0 +1,243 @@
+//! Validation of hand-written `Field` implementations
+
+use crate::Field;
+
+use core::{
+    fmt,
+    mem::{align_of, size_of, MaybeUninit},
+};
+
+/// A broken invariant of a `Field` implementation, found by [`check_field`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldError {
+    /// The field is not inside of the parent
+    OutOfBounds {
+        /// The offset of the field from the start of the parent
+        offset:      isize,
+        /// The size of the field
+        size:        usize,
+        /// The size of the parent
+        parent_size: usize,
+    },
+    /// The field isn't aligned for its type, this is expected for the fields
+    /// of packed structs, so it is only reported after every other check
+    Misaligned {
+        /// The offset of the field from the start of the parent
+        offset: usize,
+        /// The alignment of the type of the field
+        align:  usize,
+    },
+    /// `project_raw` and `project_raw_mut` project to different offsets
+    ProjectionMismatch {
+        /// The offset given by `project_raw`
+        offset:     usize,
+        /// The offset given by `project_raw_mut`
+        offset_mut: usize,
+    },
+    /// `field_offset`, `range` or `dynamic` disagree with the projection
+    OffsetMismatch {
+        /// The offset given by `project_raw`
+        projected: usize,
+        /// The offset reported by the field
+        reported:  usize,
+    },
+    /// The inverse projections don't lead back to the parent, which means
+    /// that the field isn't at the same offset in every parent
+    InverseMismatch,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            FieldError::OutOfBounds {
+                offset,
+                size,
+                parent_size,
+            } => write!(
+                f,
+                "field at offset {} of size {} is outside of its parent of \
+                 size {}",
+                offset, size, parent_size
+            ),
+            FieldError::Misaligned {
+                offset,
+                align,
+            } => write!(
+                f,
+                "field at offset {} is not aligned to {} bytes",
+                offset, align
+            ),
+            FieldError::ProjectionMismatch {
+                offset,
+                offset_mut,
+            } => write!(
+                f,
+                "`project_raw` gives offset {}, but `project_raw_mut` gives \
+                 offset {}",
+                offset, offset_mut
+            ),
+            FieldError::OffsetMismatch {
+                projected,
+                reported,
+            } => write!(
+                f,
+                "field is projected to offset {}, but reports offset {}",
+                projected, reported
+            ),
+            FieldError::InverseMismatch => {
+                f.write_str("inverse projection doesn't lead back to parent")
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FieldError {
+}
+
+/// Check the invariants of a `Field` implementation
+///
+/// This checks that
+///
+/// * the field is inside of `Parent`
+/// * `project_raw` and `project_raw_mut` project to the same field
+/// * `field_offset`, `range`, and `dynamic` agree with the projections
+/// * the inverse projections lead back to the parent, even for a parent at a
+///   different address
+/// * the field is aligned for `Type`
+///
+/// This can't prove that a `Field` is sound, but it catches most mistakes in
+/// hand-written implementations.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// use gfp_core::{check_field, Field, FieldError};
+///
+/// struct Foo {
+///     bar: u32,
+/// }
+///
+/// struct FieldBar;
+///
+/// unsafe impl Field for FieldBar {
+///     type Parent = Foo;
+///     type Type = u32;
+///
+///     unsafe fn project_raw(&self, ptr: *const Foo) -> *const u32 {
+///         &raw const (*ptr).bar
+///     }
+///
+///     unsafe fn project_raw_mut(&self, ptr: *mut Foo) -> *mut u32 {
+///         &raw mut (*ptr).bar
+///     }
+/// }
+///
+/// assert_eq!(check_field(&FieldBar), Ok(()));
+/// ```
+pub fn check_field<F: Field + ?Sized>(field: &F) -> Result<(), FieldError> {
+    let mut parents = MaybeUninit::<[F::Parent; 2]>::uninit();
+    let parent_ptr = parents.as_mut_ptr().cast::<F::Parent>();
+    let parent_addr = parent_ptr as usize;
+
+    // Safety
+    // * `parent_ptr` does point to a valid allocation of `Parent`
+    //      * it just happens to be uninitialized
+    // * we never read or write through the projections
+    let (field_ptr, field_ptr_mut) = unsafe {
+        (field.project_raw(parent_ptr), field.project_raw_mut(parent_ptr))
+    };
+
+    // the pointers are only compared by address, because they may point
+    // outside of the parent
+    let offset = (field_ptr as usize).wrapping_sub(parent_addr) as isize;
+    let offset_mut = (field_ptr_mut as usize).wrapping_sub(parent_addr);
+    let size = size_of::<F::Type>();
+    let parent_size = size_of::<F::Parent>();
+
+    let end = (offset as usize).checked_add(size);
+
+    if offset < 0 || end.is_none_or(|end| end > parent_size) {
+        return Err(FieldError::OutOfBounds {
+            offset,
+            size,
+            parent_size,
+        })
+    }
+
+    let offset = offset as usize;
+
+    if offset != offset_mut {
+        return Err(FieldError::ProjectionMismatch {
+            offset,
+            offset_mut,
+        })
+    }
+
+    let range = field.range();
+    let reported =
+        [field.field_offset(), range.start, field.dynamic().offset()];
+
+    for &reported in reported.iter() {
+        if reported != offset {
+            return Err(FieldError::OffsetMismatch {
+                projected: offset,
+                reported,
+            })
+        }
+    }
+
+    if range.len() != size {
+        return Err(FieldError::OutOfBounds {
+            offset: offset as isize,
+            size: range.len(),
+            parent_size,
+        })
+    }
+
+    // the inverse projections subtract `field_offset`, which was already
+    // checked against the first parent, so a second parent is used to catch
+    // fields that aren't at a fixed offset
+    //
+    // Dev Note: the `unsafe` inverse projections are checked by the `checked`
+    // feature, so only the wrapping ones are used here, they must agree
+    // because neither of them can be overridden
+    let other_ptr = parent_ptr.wrapping_add(1);
+
+    // Safety: see above, `other_ptr` is the second parent in `parents`
+    let (other_field, other_field_mut) = unsafe {
+        (field.project_raw(other_ptr), field.project_raw_mut(other_ptr))
+    };
+
+    let inverse = [
+        field.wrapping_inverse_project_raw(other_field) as usize,
+        field.wrapping_inverse_project_raw_mut(other_field_mut) as usize,
+    ];
+
+    if inverse.iter().any(|&addr| addr != other_ptr as usize) {
+        return Err(FieldError::InverseMismatch)
+    }
+
+    // the offset is checked instead of the address, because the parent may be
+    // less aligned than the field, and then the address depends on where the
+    // parent happens to be
+    if !offset.is_multiple_of(align_of::<F::Type>()) {
+        return Err(FieldError::Misaligned {
+            offset,
+            align: align_of::<F::Type>(),
+        })
+    }
+
+    Ok(())
+}
+
+/// Panic if a `Field` implementation is broken, used by the `checked` feature
+///
+/// Misaligned fields are allowed, since they may be fields of packed structs
+#[cfg(feature = "checked")]
+#[track_caller]
+pub(crate) fn assert_field<F: Field + ?Sized>(field: &F) {
+    match check_field(field) {
+        Ok(()) | Err(FieldError::Misaligned { .. }) => (),
+        Err(error) => panic!("invalid `Field` implementation: {}", error),
+    }
+}
@@ -0,0 +1,97 @@
+use crate::project::FindOverlap;
+
+use typsy::cmp::Any;
+
+/// A field list that has already been checked for overlapping fields
+///
+/// Projecting a `&mut Parent` into a plain field list checks the list every
+/// time, projecting into a `Disjoint` list doesn't check again. This works
+/// with every pointer that implements `ProjectAll`, including `Pin`.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Disjoint, Field, ProjectAll};
+/// use typsy::convert::Convert;
+///
+/// #[derive(Field)]
+/// struct Particle {
+///     position: f32,
+///     velocity: f32,
+/// }
+///
+/// fn main() {
+///     let fields = Particle::fields();
+///     let fields = (fields.position, fields.velocity).into_hlist();
+///     let fields = Disjoint::new(fields);
+///
+///     let mut particles = vec![
+///         Particle { position: 0.0, velocity: 1.0 },
+///         Particle { position: 2.0, velocity: -1.0 },
+///     ];
+///
+///     for particle in &mut particles {
+///         let typsy::hlist_pat!(position, velocity) =
+///             particle.project_all(fields);
+///         *position += *velocity;
+///     }
+///
+///     assert_eq!(particles[0].position, 1.0);
+///     assert_eq!(particles[1].position, 1.0);
+/// }
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Disjoint<L> {
+    list: L,
+}
+
+impl<L> Disjoint<L> {
+    /// Check that none of the fields in `list` overlap
+    ///
+    /// # Panics
+    ///
+    /// If any of the fields overlap
+    pub fn new(list: L) -> Self
+    where
+        L: Copy + for<'b> Any<'b, FindOverlap<L>>,
+    {
+        Self::try_new(list).expect("Found overlapping fields")
+    }
+
+    /// Check that none of the fields in `list` overlap, returns `None` if any
+    /// of them do
+    pub fn try_new(list: L) -> Option<Self>
+    where
+        L: Copy + for<'b> Any<'b, FindOverlap<L>>,
+    {
+        if list.any(FindOverlap::new(list)) {
+            None
+        } else {
+            Some(Self {
+                list,
+            })
+        }
+    }
+
+    /// Create a field list without checking it, this can be used in a `const`
+    ///
+    /// # Safety
+    ///
+    /// None of the fields in `list` may overlap
+    pub const unsafe fn new_unchecked(list: L) -> Self {
+        Self {
+            list,
+        }
+    }
+
+    /// Get the underlying field list
+    pub fn get(&self) -> &L {
+        &self.list
+    }
+
+    /// Get back the underlying field list
+    pub fn into_inner(self) -> L {
+        self.list
+    }
+}
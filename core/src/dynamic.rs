@@ -1,4 +1,11 @@
-use crate::Field;
+use crate::{Chain, Field};
+
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Add,
+};
 
 /// A runtime offset based `Field`. This is a more efficient version
 /// of `dyn Field<Parent = P, Type = T, Name = N>`.
@@ -26,6 +33,21 @@ impl<P, T> Dynamic<P, T> {
     ///              to a field/sub-field of type `T`
     /// * `name`   - The fully qualified name of the field
     pub unsafe fn from_offset(offset: usize) -> Self {
+        #[cfg(feature = "checked")]
+        {
+            use core::mem::size_of;
+
+            // the offset isn't checked for alignment, because the fields of
+            // packed structs may be misaligned
+            assert!(
+                offset
+                    .checked_add(size_of::<T>())
+                    .is_some_and(|end| end <= size_of::<P>()),
+                "field at offset {} is outside of its parent",
+                offset
+            );
+        }
+
         Self {
             offset,
             _mark: crate::derive::Invariant::INIT,
@@ -38,6 +60,96 @@ impl<P, T> Dynamic<P, T> {
     pub fn offset(&self) -> usize {
         self.offset
     }
+
+    /// Compose with a field of `T` by adding their offsets, this is the
+    /// flattened version of [`Field::chain`]
+    ///
+    /// ```rust
+    /// #![feature(raw_ref_op)]
+    /// # mod main {
+    /// use gfp_core::{Dynamic, Field};
+    ///
+    /// #[derive(Field)]
+    /// struct Foo {
+    ///     x: u8,
+    ///     y: Bar,
+    /// }
+    ///
+    /// #[derive(Field)]
+    /// struct Bar {
+    ///     a: u16,
+    ///     b: u32,
+    /// }
+    ///
+    /// fn main() {
+    ///     let y = Foo::fields().y.dynamic();
+    ///     let b = Bar::fields().b.dynamic();
+    ///
+    ///     let y_b: Dynamic<Foo, u32> = y.compose(b);
+    ///     assert_eq!(y_b, Foo::fields().y.chain(Bar::fields().b).into());
+    ///     assert_eq!(y_b, y + b);
+    /// }
+    /// # }
+    /// ```
+    pub fn compose<U>(self, field: Dynamic<T, U>) -> Dynamic<P, U> {
+        unsafe { Dynamic::from_offset(self.offset + field.offset) }
+    }
+}
+
+impl<P> Dynamic<P, P> {
+    /// The field that covers the whole of `P`, composing with it is a no-op
+    pub fn identity() -> Self {
+        unsafe { Self::from_offset(0) }
+    }
+}
+
+impl<A, B, C> Add<Dynamic<B, C>> for Dynamic<A, B> {
+    type Output = Dynamic<A, C>;
+
+    fn add(self, field: Dynamic<B, C>) -> Self::Output {
+        self.compose(field)
+    }
+}
+
+impl<A: Field, B: Field<Parent = A::Type>> From<Chain<A, B>>
+    for Dynamic<A::Parent, B::Type>
+{
+    fn from(chain: Chain<A, B>) -> Self {
+        chain.dynamic()
+    }
+}
+
+impl<P, T> PartialEq for Dynamic<P, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset
+    }
+}
+
+impl<P, T> Eq for Dynamic<P, T> {
+}
+
+impl<P, T> PartialOrd for Dynamic<P, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P, T> Ord for Dynamic<P, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.offset.cmp(&other.offset)
+    }
+}
+
+impl<P, T> Hash for Dynamic<P, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.offset.hash(state)
+    }
+}
+
+impl<P, T> fmt::Debug for Dynamic<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dynamic").field("offset", &self.offset).finish()
+    }
 }
 
 unsafe impl<P, T> Field for Dynamic<P, T> {
@@ -0,0 +1,217 @@
+use crate::{
+    reflect::{AsAny, AsAnyMut, Resolved},
+    Dynamic,
+    Field,
+    PathError,
+    Reflect,
+};
+
+use core::{
+    any::{type_name, Any, TypeId},
+    fmt,
+    mem,
+    ops::Range,
+};
+
+/// A runtime offset based field with its type erased, so that fields of
+/// different types can be stored together
+///
+/// Generated from [`Dynamic::erase`], or looked up with
+/// [`ErasedField::from_path`]
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{ErasedField, Field, ProjectTo};
+///
+/// #[derive(Field)]
+/// struct Config {
+///     name:    &'static str,
+///     retries: u32,
+/// }
+///
+/// fn main() {
+///     let config = Config {
+///         name:    "config",
+///         retries: 3,
+///     };
+///
+///     let fields: Vec<ErasedField<Config>> = vec![
+///         Config::fields().name.dynamic().erase(),
+///         Config::fields().retries.dynamic().erase(),
+///     ];
+///
+///     let retries = fields[1].downcast::<u32>().unwrap();
+///     assert_eq!(*config.project_to(retries), 3);
+///
+///     let name = fields[0].project(&config);
+///     assert_eq!(name.downcast_ref::<&str>(), Some(&"config"));
+/// }
+/// # }
+/// ```
+pub struct ErasedField<P> {
+    offset:     usize,
+    size:       usize,
+    align:      usize,
+    type_id:    TypeId,
+    type_name:  fn() -> &'static str,
+    as_any:     AsAny,
+    as_any_mut: AsAnyMut,
+    _mark:      crate::derive::Invariant<*const P>,
+}
+
+impl<P> Copy for ErasedField<P> {
+}
+impl<P> Clone for ErasedField<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P, T: 'static> Dynamic<P, T> {
+    /// Erase the type of the field
+    pub fn erase(self) -> ErasedField<P> {
+        ErasedField {
+            offset:     self.offset(),
+            size:       mem::size_of::<T>(),
+            align:      mem::align_of::<T>(),
+            type_id:    TypeId::of::<T>(),
+            type_name:  type_name::<T>,
+            as_any:     crate::derive::as_any::<T>,
+            as_any_mut: crate::derive::as_any_mut::<T>,
+            _mark:      crate::derive::Invariant::INIT,
+        }
+    }
+}
+
+impl<P, T: 'static> From<Dynamic<P, T>> for ErasedField<P> {
+    fn from(field: Dynamic<P, T>) -> Self {
+        field.erase()
+    }
+}
+
+impl<P: Reflect> ErasedField<P> {
+    /// Look up a field of `P` by name
+    pub fn from_name(name: &str) -> Result<Self, PathError<'_>> {
+        Self::from_resolved(Resolved::from_name::<P>(name)?)
+    }
+
+    /// Look up a field of `P` by a dotted path of names, like `"y.c.r"`,
+    /// see [`Dynamic::from_path`]
+    pub fn from_path(path: &str) -> Result<Self, PathError<'_>> {
+        Self::from_resolved(Resolved::from_path::<P>(path)?)
+    }
+
+    fn from_resolved(field: Resolved<'_>) -> Result<Self, PathError<'_>> {
+        let (type_id, as_any, as_any_mut) = field.any()?;
+
+        Ok(Self {
+            offset: field.offset,
+            size: field.info.size(),
+            align: field.info.align(),
+            type_id,
+            type_name: field.info.type_name,
+            as_any,
+            as_any_mut,
+            _mark: crate::derive::Invariant::INIT,
+        })
+    }
+}
+
+impl<P> ErasedField<P> {
+    /// The offset of the field in bytes from the start of `P`
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The size of the field in bytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment of the field in bytes
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    /// The range of bytes covered by the field
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.size
+    }
+
+    /// The `TypeId` of the type of the field
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The name of the type of the field
+    pub fn type_name(&self) -> &'static str {
+        (self.type_name)()
+    }
+
+    /// Checks if the field is of type `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Recover the typed field, if the field is of type `T`
+    pub fn downcast<T: 'static>(&self) -> Option<Dynamic<P, T>> {
+        if self.is::<T>() {
+            unsafe { Some(Dynamic::from_offset(self.offset)) }
+        } else {
+            None
+        }
+    }
+
+    /// Project a reference to the parent to the field
+    pub fn project<'a>(&self, parent: &'a P) -> &'a dyn Any {
+        let ptr = (parent as *const P).cast::<u8>().wrapping_add(self.offset);
+        unsafe { &*(self.as_any)(ptr) }
+    }
+
+    /// Project a mutable reference to the parent to the field
+    pub fn project_mut<'a>(&self, parent: &'a mut P) -> &'a mut dyn Any {
+        unsafe { &mut *self.project_raw_mut(parent) }
+    }
+
+    /// Project a mutable raw pointer to the parent to the field
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must point to a valid allocation of `P`
+    pub(crate) unsafe fn project_raw_mut(&self, ptr: *mut P) -> *mut dyn Any {
+        (self.as_any_mut)(ptr.cast::<u8>().add(self.offset))
+    }
+
+    /// Checks if two fields of the same parent could alias each other
+    pub fn is_overlapping(&self, other: &ErasedField<P>) -> bool {
+        crate::project::is_overlapping(self.range(), other.range())
+    }
+
+    /// Checks if this field could alias a typed field of the same parent
+    pub fn is_overlapping_field<F>(&self, other: &F) -> bool
+    where
+        F: Field<Parent = P>,
+    {
+        crate::project::is_overlapping(self.range(), other.range())
+    }
+}
+
+impl<P> PartialEq for ErasedField<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset && self.type_id == other.type_id
+    }
+}
+
+impl<P> Eq for ErasedField<P> {
+}
+
+impl<P> fmt::Debug for ErasedField<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErasedField")
+            .field("offset", &self.offset)
+            .field("size", &self.size)
+            .field("type_name", &self.type_name())
+            .finish()
+    }
+}
@@ -0,0 +1,401 @@
+use crate::{
+    field_set::check_disjoint,
+    project::{from_mut::PtrToRefMut, is_overlapping, PtrToRef},
+    type_list::{
+        FieldList, ProjectRaw, ProjectRawMut, Projected, ProjectedMut, Ranges,
+    },
+    BorrowError, Field,
+};
+
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut, Range},
+};
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    vec::Vec,
+};
+use typsy::{
+    cmp::Any,
+    map::{Map, Mapped},
+};
+
+/// A lock that can lock each field of `Parent` on its own
+///
+/// Fields are locked by the bytes that they cover, so any number of threads
+/// can lock fields at the same time, as long as the fields don't overlap.
+/// Locking a field that overlaps a locked field blocks until it is unlocked.
+///
+/// Like a `Mutex`, locking a field that the current thread already holds
+/// will never return.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Field, FieldLock};
+///
+/// #[derive(Default, Field)]
+/// struct Stats {
+///     hits:   u64,
+///     misses: u64,
+///     names:  Vec<String>,
+/// }
+///
+/// fn main() {
+///     let fields = Stats::fields();
+///     let stats = FieldLock::new(Stats::default());
+///
+///     std::thread::scope(|scope| {
+///         for _ in 0..4 {
+///             scope.spawn(|| *stats.lock(fields.hits) += 1);
+///             scope.spawn(|| *stats.lock(fields.misses) += 2);
+///         }
+///
+///         let mut guard =
+///             stats.lock_all(typsy::hlist!(fields.hits, fields.names));
+///         let typsy::hlist_pat!(hits, names) = guard.get_mut();
+///         names.push(hits.to_string());
+///     });
+///
+///     let stats = stats.into_inner();
+///     assert_eq!((stats.hits, stats.misses), (4, 8));
+///     assert_eq!(stats.names.len(), 1);
+/// }
+/// # }
+/// ```
+///
+/// Every locked field must be `Send`, even if `Parent` is, because a field
+/// may be locked on a different thread than the rest of `Parent`
+///
+/// ```compile_fail
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Field, FieldLock};
+/// use std::rc::Rc;
+///
+/// #[derive(Field)]
+/// struct Local {
+///     rc: Rc<u32>,
+/// }
+///
+/// // Safety: `Local` is only used by one thread at a time
+/// unsafe impl Send for Local {}
+///
+/// fn main() {
+///     let fields = Local::fields();
+///     let local = FieldLock::new(Local { rc: Rc::new(0) });
+///
+///     std::thread::scope(|scope| {
+///         scope.spawn(|| Rc::clone(&local.lock(fields.rc)));
+///         let _ = Rc::clone(&local.lock(fields.rc));
+///     });
+/// }
+/// # }
+/// ```
+pub struct FieldLock<Parent> {
+    locked:   Mutex<Vec<Range<usize>>>,
+    unlocked: Condvar,
+    value:    UnsafeCell<Parent>,
+}
+
+// Safety: the fields are only ever accessed by the thread that locked them,
+// like a `Mutex`. Unlike a `Mutex`, each field can be locked and then used on
+// a different thread than the others, so `lock` and `lock_all` also require
+// every locked field to be `Send`
+unsafe impl<Parent: Send> Send for FieldLock<Parent> {
+}
+unsafe impl<Parent: Send> Sync for FieldLock<Parent> {
+}
+
+/// A locked field of a [`FieldLock`], the field is unlocked when this is
+/// dropped
+pub struct FieldGuard<'a, Parent, T> {
+    lock:  &'a FieldLock<Parent>,
+    value: *mut T,
+    range: Range<usize>,
+}
+
+/// A locked list of fields of a [`FieldLock`], the fields are unlocked when
+/// this is dropped
+pub struct FieldListGuard<'a, Parent, L> {
+    lock:   &'a FieldLock<Parent>,
+    fields: L,
+    ranges: Vec<Range<usize>>,
+    mark:   PhantomData<&'a mut Parent>,
+}
+
+// Safety: the guard acts like a `&mut T` to the locked field
+unsafe impl<Parent: Send, T: Send> Send for FieldGuard<'_, Parent, T> {
+}
+unsafe impl<Parent: Send, T: Sync> Sync for FieldGuard<'_, Parent, T> {
+}
+
+impl<Parent> FieldLock<Parent> {
+    /// Create a new lock with no locked fields
+    pub fn new(value: Parent) -> Self {
+        Self {
+            locked:   Mutex::new(Vec::new()),
+            unlocked: Condvar::new(),
+            value:    UnsafeCell::new(value),
+        }
+    }
+
+    /// Get back the value
+    pub fn into_inner(self) -> Parent {
+        self.value.into_inner()
+    }
+
+    /// Get a unique reference to the value, no locking is needed because
+    /// there can't be any guards
+    pub fn get_mut(&mut self) -> &mut Parent {
+        self.value.get_mut()
+    }
+
+    // the ranges are only changed while the mutex is locked, and are never
+    // left in an invalid state, so poisoning can be ignored
+    fn locked(&self) -> MutexGuard<'_, Vec<Range<usize>>> {
+        self.locked.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn find_overlap(
+        locked: &[Range<usize>],
+        ranges: &[Range<usize>],
+    ) -> Option<BorrowError> {
+        ranges.iter().find_map(|range| {
+            locked
+                .iter()
+                .find(|other| is_overlapping(range.clone(), (*other).clone()))
+                .map(|other| BorrowError {
+                    requested: range.clone(),
+                    borrowed:  other.clone(),
+                })
+        })
+    }
+
+    fn lock_ranges(&self, ranges: &[Range<usize>]) {
+        let mut locked = self.locked();
+
+        while Self::find_overlap(&locked, ranges).is_some() {
+            locked = self
+                .unlocked
+                .wait(locked)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+
+        // zero sized fields can't alias anything, so they aren't tracked
+        locked.extend(ranges.iter().filter(|r| !r.is_empty()).cloned());
+    }
+
+    fn try_lock_ranges(
+        &self,
+        ranges: &[Range<usize>],
+    ) -> Result<(), BorrowError> {
+        let mut locked = self.locked();
+
+        if let Some(err) = Self::find_overlap(&locked, ranges) {
+            return Err(err)
+        }
+
+        locked.extend(ranges.iter().filter(|r| !r.is_empty()).cloned());
+
+        Ok(())
+    }
+
+    fn unlock_ranges(&self, ranges: &[Range<usize>]) {
+        let mut locked = self.locked();
+
+        // locked ranges never overlap, so there is at most one that matches
+        for range in ranges {
+            if let Some(index) = locked.iter().position(|r| r == range) {
+                locked.swap_remove(index);
+            }
+        }
+
+        drop(locked);
+        self.unlocked.notify_all();
+    }
+
+    fn list_ranges<L>(list: L) -> Vec<Range<usize>>
+    where
+        L: for<'v> Any<'v, Ranges<'v>>,
+    {
+        let mut ranges = Vec::new();
+        list.any(Ranges(&mut ranges));
+
+        if let Err(err) = check_disjoint(ranges.iter().cloned()) {
+            panic!("Found overlapping fields: {}", err)
+        }
+
+        ranges
+    }
+
+    /// Lock the field, blocking until it doesn't overlap any locked fields
+    pub fn lock<F>(&self, field: F) -> FieldGuard<'_, Parent, F::Type>
+    where
+        F: Field<Parent = Parent>,
+        F::Type: Send,
+    {
+        let range = field.range();
+        self.lock_ranges(core::slice::from_ref(&range));
+        self.guard(field, range)
+    }
+
+    /// Lock the field, if it doesn't overlap any locked fields
+    pub fn try_lock<F>(
+        &self,
+        field: F,
+    ) -> Result<FieldGuard<'_, Parent, F::Type>, BorrowError>
+    where
+        F: Field<Parent = Parent>,
+        F::Type: Send,
+    {
+        let range = field.range();
+        self.try_lock_ranges(core::slice::from_ref(&range))?;
+        Ok(self.guard(field, range))
+    }
+
+    fn guard<F>(
+        &self,
+        field: F,
+        range: Range<usize>,
+    ) -> FieldGuard<'_, Parent, F::Type>
+    where
+        F: Field<Parent = Parent>,
+    {
+        FieldGuard {
+            lock: self,
+            // Safety: `value` is a valid `Parent` that lives as long as
+            // `self`, and the field was locked
+            value: unsafe { field.project_raw_mut(self.value.get()) },
+            range,
+        }
+    }
+
+    /// Lock all of the fields in the list at once, blocking until none of
+    /// them overlap any locked fields
+    ///
+    /// # Panics
+    ///
+    /// If any of the fields in the list overlap each other
+    pub fn lock_all<'s, L>(&'s self, list: L) -> FieldListGuard<'s, Parent, L>
+    where
+        L: FieldList<Parent> + Copy + for<'v> Any<'v, Ranges<'v>>,
+        ProjectedMut<Parent, L>: Map<PtrToRefMut<'s>>,
+        Mapped<ProjectedMut<Parent, L>, PtrToRefMut<'s>>: Send,
+    {
+        let ranges = Self::list_ranges(list);
+        self.lock_ranges(&ranges);
+
+        FieldListGuard {
+            lock: self,
+            fields: list,
+            ranges,
+            mark: PhantomData,
+        }
+    }
+
+    /// Lock all of the fields in the list at once, if none of them overlap
+    /// any locked fields
+    ///
+    /// # Panics
+    ///
+    /// If any of the fields in the list overlap each other
+    pub fn try_lock_all<'s, L>(
+        &'s self,
+        list: L,
+    ) -> Result<FieldListGuard<'s, Parent, L>, BorrowError>
+    where
+        L: FieldList<Parent> + Copy + for<'v> Any<'v, Ranges<'v>>,
+        ProjectedMut<Parent, L>: Map<PtrToRefMut<'s>>,
+        Mapped<ProjectedMut<Parent, L>, PtrToRefMut<'s>>: Send,
+    {
+        let ranges = Self::list_ranges(list);
+        self.try_lock_ranges(&ranges)?;
+
+        Ok(FieldListGuard {
+            lock: self,
+            fields: list,
+            ranges,
+            mark: PhantomData,
+        })
+    }
+}
+
+impl<Parent: Default> Default for FieldLock<Parent> {
+    fn default() -> Self {
+        Self::new(Parent::default())
+    }
+}
+
+impl<Parent> fmt::Debug for FieldLock<Parent> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldLock")
+            .field("locked", &*self.locked())
+            .finish()
+    }
+}
+
+impl<Parent, T> Deref for FieldGuard<'_, Parent, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.value }
+    }
+}
+
+impl<Parent, T> DerefMut for FieldGuard<'_, Parent, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.value }
+    }
+}
+
+impl<Parent, T> Drop for FieldGuard<'_, Parent, T> {
+    fn drop(&mut self) {
+        self.lock.unlock_ranges(core::slice::from_ref(&self.range));
+    }
+}
+
+impl<Parent, T: fmt::Debug> fmt::Debug for FieldGuard<'_, Parent, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<Parent, L: FieldList<Parent> + Copy> FieldListGuard<'_, Parent, L> {
+    /// Get shared references to all of the locked fields
+    pub fn get<'s>(&'s self) -> Mapped<Projected<Parent, L>, PtrToRef<'s>>
+    where
+        Projected<Parent, L>: Map<PtrToRef<'s>>,
+    {
+        // Safety: the fields are locked, and are borrowed from `self`
+        unsafe {
+            self.fields
+                .map(ProjectRaw::new(self.lock.value.get()))
+                .map(PtrToRef(PhantomData))
+        }
+    }
+
+    /// Get unique references to all of the locked fields
+    pub fn get_mut<'s>(
+        &'s mut self,
+    ) -> Mapped<ProjectedMut<Parent, L>, PtrToRefMut<'s>>
+    where
+        ProjectedMut<Parent, L>: Map<PtrToRefMut<'s>>,
+    {
+        // Safety: the fields are locked and don't overlap each other, and are
+        // borrowed from `self`
+        unsafe {
+            self.fields
+                .map(ProjectRawMut::new(self.lock.value.get()))
+                .map(PtrToRefMut(PhantomData))
+        }
+    }
+}
+
+impl<Parent, L> Drop for FieldListGuard<'_, Parent, L> {
+    fn drop(&mut self) {
+        self.lock.unlock_ranges(&self.ranges);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{ErasedField, Field};
+
+use core::{any::Any, array, fmt, ops::Range};
+use std::vec::Vec;
+
+/// A set of disjoint fields of the same type, chosen at runtime
+///
+/// The fields are checked once when the set is created, so projecting a
+/// mutable reference to the parent into all of the fields is cheap.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Dynamic, Field, FieldSet};
+///
+/// #[derive(Field)]
+/// struct Row {
+///     id:    u32,
+///     price: u32,
+///     stock: u32,
+/// }
+///
+/// fn main() {
+///     let mut row = Row {
+///         id:    0,
+///         price: 10,
+///         stock: 3,
+///     };
+///
+///     let columns = ["stock", "price"]
+///         .iter()
+///         .map(|name| Dynamic::<Row, u32>::from_name(name).unwrap())
+///         .collect();
+///
+///     let editable = FieldSet::new(columns).unwrap();
+///
+///     for value in editable.project_mut(&mut row) {
+///         *value *= 2;
+///     }
+///
+///     assert_eq!((row.id, row.price, row.stock), (0, 20, 6));
+/// }
+/// # }
+/// ```
+pub struct FieldSet<F> {
+    fields: Vec<F>,
+}
+
+/// The error returned when the fields of a [`FieldSet`] overlap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverlapError {
+    /// The index of the first overlapping field
+    pub first:  usize,
+    /// The index of the second overlapping field
+    pub second: usize,
+}
+
+impl fmt::Display for OverlapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fields {} and {} overlap", self.first, self.second)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OverlapError {
+}
+
+/// Check that none of the ranges overlap, in `O(n log n)`
+pub(crate) fn check_disjoint<I>(ranges: I) -> Result<(), OverlapError>
+where
+    I: IntoIterator<Item = Range<usize>>,
+{
+    // empty ranges represent zero sized types, which can't overlap
+    let mut ranges = ranges
+        .into_iter()
+        .enumerate()
+        .filter(|(_, range)| !range.is_empty())
+        .collect::<Vec<_>>();
+
+    ranges.sort_unstable_by_key(|(_, range)| range.start);
+
+    let mut ranges = ranges.into_iter();
+    let mut last = match ranges.next() {
+        Some(range) => range,
+        None => return Ok(()),
+    };
+
+    for (index, range) in ranges {
+        if range.start < last.1.end {
+            return Err(OverlapError {
+                first:  last.0.min(index),
+                second: last.0.max(index),
+            })
+        }
+
+        last = (index, range);
+    }
+
+    Ok(())
+}
+
+impl<F> FieldSet<F> {
+    /// The fields in the set, in the order that they were given
+    pub fn fields(&self) -> &[F] {
+        &self.fields
+    }
+
+    /// The number of fields in the set
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Checks if the set has no fields
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Get back the fields in the set
+    pub fn into_inner(self) -> Vec<F> {
+        self.fields
+    }
+}
+
+impl<F: Field> FieldSet<F> {
+    /// Create a new set, if none of the fields overlap
+    pub fn new(fields: Vec<F>) -> Result<Self, OverlapError> {
+        check_disjoint(fields.iter().map(Field::range))?;
+
+        Ok(Self {
+            fields,
+        })
+    }
+
+    /// Project a reference to the parent to every field
+    pub fn project<'a>(&self, parent: &'a F::Parent) -> Vec<&'a F::Type> {
+        self.fields
+            .iter()
+            .map(|field| unsafe { &*field.project_raw(parent) })
+            .collect()
+    }
+
+    /// Project a mutable reference to the parent to every field
+    pub fn project_mut<'a>(
+        &self,
+        parent: &'a mut F::Parent,
+    ) -> Vec<&'a mut F::Type> {
+        let parent: *mut F::Parent = parent;
+
+        self.fields
+            .iter()
+            .map(|field| unsafe { &mut *field.project_raw_mut(parent) })
+            .collect()
+    }
+
+    /// Project a mutable reference to the parent to every field, without
+    /// allocating
+    ///
+    /// This returns `None` if the set doesn't have exactly `N` fields
+    pub fn project_array_mut<'a, const N: usize>(
+        &self,
+        parent: &'a mut F::Parent,
+    ) -> Option<[&'a mut F::Type; N]> {
+        if self.fields.len() != N {
+            return None
+        }
+
+        let parent: *mut F::Parent = parent;
+
+        Some(array::from_fn(|i| unsafe {
+            &mut *self.fields[i].project_raw_mut(parent)
+        }))
+    }
+}
+
+impl<P> FieldSet<ErasedField<P>> {
+    /// Create a new set of erased fields, if none of the fields overlap
+    pub fn new_erased(
+        fields: Vec<ErasedField<P>>,
+    ) -> Result<Self, OverlapError> {
+        check_disjoint(fields.iter().map(ErasedField::range))?;
+
+        Ok(Self {
+            fields,
+        })
+    }
+
+    /// Project a reference to the parent to every field
+    pub fn project<'a>(&self, parent: &'a P) -> Vec<&'a dyn Any> {
+        self.fields.iter().map(|field| field.project(parent)).collect()
+    }
+
+    /// Project a mutable reference to the parent to every field
+    pub fn project_mut<'a>(&self, parent: &'a mut P) -> Vec<&'a mut dyn Any> {
+        let parent: *mut P = parent;
+
+        self.fields
+            .iter()
+            .map(|field| unsafe { &mut *field.project_raw_mut(parent) })
+            .collect()
+    }
+}
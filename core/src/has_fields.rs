@@ -0,0 +1,239 @@
+use crate::{
+    derive::{Child, Prefix},
+    Field, Identity,
+};
+
+/// Types with derived field types
+///
+/// This is implemented by `#[derive(Field)]` for structs and tuple structs,
+/// which lets generic code name the field types of any derived type.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Field, HasFields};
+///
+/// #[derive(Field)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// fn offsets<T: HasFields>(f: impl Fn(T::Fields) -> usize) -> usize {
+///     f(T::FIELDS)
+/// }
+///
+/// fn main() {
+///     let y = offsets::<Point>(|fields| fields.y.field_offset());
+///
+///     assert_eq!(y, Point::fields().y.field_offset());
+/// }
+/// # }
+/// ```
+pub trait HasFields {
+    /// The struct that holds all of the field types, `{Type}Fields`
+    type Fields: Copy;
+
+    /// All of the field types
+    const FIELDS: Self::Fields;
+
+    /// The number of fields
+    const FIELD_COUNT: usize;
+
+    /// Get all of the field types
+    fn fields() -> Self::Fields {
+        Self::FIELDS
+    }
+}
+
+/// Unions with derived field types
+///
+/// This is implemented by `#[derive(Field)]` for unions, see [`HasFields`]
+pub trait HasUnionFields {
+    /// The struct that holds all of the field types, `{Type}Fields`
+    type Fields: Copy;
+
+    /// The number of fields
+    const FIELD_COUNT: usize;
+
+    /// Get all of the field types
+    ///
+    /// # Safety
+    ///
+    /// The same as the derived `fields` function, projecting to more than one
+    /// field of a union may alias unique references
+    unsafe fn fields() -> Self::Fields;
+}
+
+/// Types with a field named `N`
+///
+/// This is implemented for every field of a struct with derived field types,
+/// and names are written with `field_name!`. This lets generic code accept any
+/// type that has a field with a given name and type.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{field_name, Field, HasField, ProjectTo};
+///
+/// #[derive(Field)]
+/// struct User {
+///     id:   u64,
+///     name: String,
+/// }
+///
+/// #[derive(Field)]
+/// struct Order {
+///     id:    u64,
+///     total: u32,
+/// }
+///
+/// fn id<T: HasField<field_name!(id), Type = u64>>(value: &T) -> u64 {
+///     *value.project_to(T::FIELD)
+/// }
+///
+/// fn main() {
+///     let user = User {
+///         id:   1,
+///         name: String::from("Ferris"),
+///     };
+///     let order = Order {
+///         id:    2,
+///         total: 100,
+///     };
+///
+///     assert_eq!(id(&user), 1);
+///     assert_eq!(id(&order), 2);
+/// }
+/// # }
+/// ```
+pub trait HasField<N>: Sized {
+    /// The type of the field
+    type Type;
+
+    /// The field type of the field
+    type Field: Field<Parent = Self, Type = Self::Type> + Copy;
+
+    /// The field
+    const FIELD: Self::Field;
+}
+
+impl<N, T> HasField<N> for T
+where
+    T: Child<N, Identity<T>>,
+    T::Field: Field<Parent = T> + Copy,
+{
+    type Type = <T::Field as Field>::Type;
+    type Field = T::Field;
+
+    const FIELD: Self::Field = <T::Field as Prefix>::INIT;
+}
+
+/// Types with a field at position `N`
+///
+/// This is implemented for every field of a struct with derived field types,
+/// fields are counted in the order that they are declared in, for both tuple
+/// structs and structs with named fields. See [`HasFields::FIELD_COUNT`] for
+/// the number of fields.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Field, FieldAt, HasFields, ProjectTo};
+///
+/// #[derive(Field)]
+/// struct Row {
+///     id:    u64,
+///     score: u32,
+/// }
+///
+/// #[derive(Field)]
+/// struct Pair(u64, u32);
+///
+/// fn key<T: FieldAt<0, Type = u64>>(value: &T) -> u64 {
+///     *value.project_to(T::FIELD)
+/// }
+///
+/// fn main() {
+///     assert_eq!(key(&Row { id: 3, score: 10 }), 3);
+///     assert_eq!(key(&Pair(4, 20)), 4);
+///
+///     assert_eq!(Row::FIELD_COUNT, 2);
+/// }
+/// # }
+/// ```
+pub trait FieldAt<const N: usize>: Sized {
+    /// The type of the field
+    type Type;
+
+    /// The field type of the field
+    type Field: Field<Parent = Self, Type = Self::Type> + Copy;
+
+    /// The field
+    const FIELD: Self::Field;
+}
+
+/// Copy the fields of `Src` into a type with fields of the same names
+///
+/// This is implemented by `#[derive(Field)]` with `#[field(copy_from(Src))]`,
+/// see the derive for more details. Every field is converted with `From`
+/// from the field of `Src` with the same name, so a field that `Src` doesn't
+/// have, or that can't be converted, fails to compile. Fields marked with
+/// `#[field(skip_copy)]` are left as they are.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{CopyNamed, Field};
+///
+/// #[derive(Field)]
+/// struct UserRow {
+///     id:      u32,
+///     name:    String,
+///     deleted: bool,
+/// }
+///
+/// #[derive(Default, Field)]
+/// #[field(copy_from(UserRow))]
+/// struct User {
+///     id:     u64,
+///     name:   String,
+///     #[field(skip_copy)]
+///     visits: u32,
+/// }
+///
+/// fn main() {
+///     let row = UserRow {
+///         id:      1,
+///         name:    String::from("ferris"),
+///         deleted: false,
+///     };
+///
+///     let mut user = User::from_named(&row);
+///     assert_eq!((user.id, user.name.as_str()), (1, "ferris"));
+///
+///     user.visits = 3;
+///     user.copy_named(&UserRow {
+///         id:      2,
+///         name:    String::from("corro"),
+///         deleted: true,
+///     });
+///     assert_eq!((user.id, user.visits), (2, 3));
+/// }
+/// # }
+/// ```
+pub trait CopyNamed<Src> {
+    /// Copy every field with the same name from `src` into `self`
+    fn copy_named(&mut self, src: &Src);
+
+    /// Create a value from the fields of `src`, skipped fields are set to
+    /// their default value
+    fn from_named(src: &Src) -> Self
+    where
+        Self: Default,
+    {
+        let mut value = Self::default();
+        value.copy_named(src);
+        value
+    }
+}
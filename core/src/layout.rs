@@ -0,0 +1,247 @@
+//! Layout reports of derived types
+
+use crate::{FieldInfo, Reflect};
+
+use core::{any::type_name, fmt, mem, ops::Range};
+
+/// The layout of a type with every field and padding gap, in offset order
+///
+/// Generated from [`Reflect::layout`]
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Field, Reflect};
+///
+/// #[derive(Field)]
+/// #[repr(C)]
+/// struct Foo {
+///     a: u8,
+///     b: u32,
+///     c: u16,
+/// }
+///
+/// fn main() {
+///     let layout = Foo::layout();
+///
+///     assert_eq!(layout.padding(), 5);
+///     assert_eq!(
+///         layout.lines().to_string(),
+///         "type size=12 align=4
+/// field name=a offset=0 size=1 align=1
+/// padding offset=1 size=3
+/// field name=b offset=4 size=4 align=4
+/// field name=c offset=8 size=2 align=2
+/// padding offset=10 size=2
+/// "
+///     );
+/// }
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct TypeLayout {
+    name:   &'static str,
+    size:   usize,
+    align:  usize,
+    fields: &'static [FieldInfo],
+}
+
+/// A single entry of a [`TypeLayout`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutEntry {
+    /// A field
+    Field(&'static FieldInfo),
+    /// Padding bytes between or after fields
+    Padding(Range<usize>),
+}
+
+impl TypeLayout {
+    /// Get the layout of `T`
+    pub fn of<T: Reflect>() -> Self {
+        Self {
+            name:   type_name::<T>(),
+            size:   mem::size_of::<T>(),
+            align:  mem::align_of::<T>(),
+            fields: T::FIELD_INFO,
+        }
+    }
+
+    /// The name of the type
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The size of the type in bytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment of the type in bytes
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    /// Every field and padding gap, in offset order
+    pub fn entries(&self) -> LayoutEntries {
+        LayoutEntries {
+            layout:  *self,
+            cursor:  None,
+            covered: 0,
+            pending: None,
+        }
+    }
+
+    /// The total number of padding bytes
+    pub fn padding(&self) -> usize {
+        self.entries()
+            .map(|entry| match entry {
+                LayoutEntry::Field(_) => 0,
+                LayoutEntry::Padding(range) => range.len(),
+            })
+            .sum()
+    }
+
+    /// Render the layout in a stable line-based format, which is meant to be
+    /// read by tools
+    ///
+    /// The first line is `type size=S align=A`, followed by one line per
+    /// entry, either `field name=N offset=O size=S align=A` or
+    /// `padding offset=O size=S`. Type names are left out, because they are
+    /// not stable between compiler versions.
+    pub fn lines(&self) -> LayoutLines {
+        LayoutLines(*self)
+    }
+}
+
+/// An iterator over the entries of a [`TypeLayout`]
+pub struct LayoutEntries {
+    layout:  TypeLayout,
+    // the offset and index of the last field that was yielded
+    cursor:  Option<(usize, usize)>,
+    // the end of the bytes covered by fields so far
+    covered: usize,
+    pending: Option<&'static FieldInfo>,
+}
+
+impl Iterator for LayoutEntries {
+    type Item = LayoutEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(field) = self.pending.take() {
+            self.covered = self.covered.max(field.range().end);
+            return Some(LayoutEntry::Field(field))
+        }
+
+        // the fields aren't sorted by offset, so search for the next one
+        let cursor = self.cursor;
+        let next = self
+            .layout
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| ((field.offset(), index), field))
+            .filter(|&(key, _)| cursor.is_none_or(|cursor| key > cursor))
+            .min_by_key(|&(key, _)| key);
+
+        match next {
+            Some((key, field)) => {
+                self.cursor = Some(key);
+
+                if field.offset() > self.covered {
+                    let padding = self.covered..field.offset();
+                    self.pending = Some(field);
+                    Some(LayoutEntry::Padding(padding))
+                } else {
+                    self.covered = self.covered.max(field.range().end);
+                    Some(LayoutEntry::Field(field))
+                }
+            },
+            None if self.covered < self.layout.size => {
+                let padding = self.covered..self.layout.size;
+                self.covered = self.layout.size;
+                Some(LayoutEntry::Padding(padding))
+            },
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for TypeLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} (size {}, align {})",
+            self.name, self.size, self.align
+        )?;
+
+        for entry in self.entries() {
+            match entry {
+                LayoutEntry::Field(field) => {
+                    let range = field.range();
+                    writeln!(
+                        f,
+                        "  {:>5}..{:<5} {}: {}",
+                        range.start,
+                        range.end,
+                        field.name(),
+                        field.type_name()
+                    )?
+                },
+                LayoutEntry::Padding(range) => {
+                    writeln!(
+                        f,
+                        "  {:>5}..{:<5} <{} bytes of padding>",
+                        range.start,
+                        range.end,
+                        range.len()
+                    )?
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for TypeLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeLayout")
+            .field("name", &self.name)
+            .field("size", &self.size)
+            .field("align", &self.align)
+            .field("fields", &self.fields)
+            .finish()
+    }
+}
+
+/// The line-based format of a [`TypeLayout`], see [`TypeLayout::lines`]
+pub struct LayoutLines(TypeLayout);
+
+impl fmt::Display for LayoutLines {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let layout = &self.0;
+
+        writeln!(f, "type size={} align={}", layout.size, layout.align)?;
+
+        for entry in layout.entries() {
+            match entry {
+                LayoutEntry::Field(field) => writeln!(
+                    f,
+                    "field name={} offset={} size={} align={}",
+                    field.name(),
+                    field.offset(),
+                    field.size(),
+                    field.align()
+                )?,
+                LayoutEntry::Padding(range) => writeln!(
+                    f,
+                    "padding offset={} size={}",
+                    range.start,
+                    range.len()
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -11,34 +11,92 @@
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
 extern crate alloc as std;
 
+mod access;
+mod bits;
+mod byte_view;
+mod bytes;
 mod chain;
+mod check;
+mod disjoint;
 mod dynamic;
+mod erased;
+#[cfg(feature = "std")]
+mod field_lock;
+#[cfg(feature = "alloc")]
+mod field_set;
+mod has_fields;
+mod layout;
 #[doc(hidden)]
 pub mod macros;
+mod partial;
 mod pin;
+mod pod;
 mod project;
+mod reflect;
+mod relative;
+#[cfg(feature = "alloc")]
+mod split_borrow;
 mod unchecked_project;
 
 #[doc(hidden)]
 pub mod type_list;
 
-pub use self::{chain::*, dynamic::Dynamic, pin::*};
-pub use gfp_derive::Field;
+pub use self::{
+    access::{Shared, Unique},
+    bits::{BitInt, BitMut, BitRef, BitVolatile, Bits},
+    byte_view::{ByteView, ByteViewError},
+    bytes::{clone_fields, copy_fields, fields_len, read_fields, write_fields},
+    chain::*,
+    check::{check_field, FieldError},
+    disjoint::Disjoint,
+    dynamic::Dynamic,
+    erased::ErasedField,
+    has_fields::{CopyNamed, FieldAt, HasField, HasFields, HasUnionFields},
+    layout::{LayoutEntries, LayoutEntry, LayoutLines, TypeLayout},
+    partial::Partial,
+    pin::*,
+    pod::{AsBytes, Pod},
+    reflect::{FieldInfo, PathError, Reflect},
+    relative::{RelOffset, RelPtr},
+};
+#[cfg(feature = "alloc")]
+pub use self::{
+    field_set::{FieldSet, OverlapError},
+    split_borrow::{BorrowError, SplitBorrow, SplitMut},
+};
+#[cfg(feature = "std")]
+pub use self::field_lock::{FieldGuard, FieldListGuard, FieldLock};
+pub use gfp_derive::{field_name, field_path, Field, FieldPath};
 
 use core::{marker::PhantomData, ops::Range};
 
 #[doc(hidden)]
 #[macro_export]
 macro_rules! ptr_project {
-    ($mut:ident $ptr:ident $field:ident) => {
+    ($mut:ident $ptr:ident $field:tt) => {
         &raw $mut (*$ptr).$field
     }
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_offset {
+    ($parent:ty => $field:tt) => {{
+        let parent = ::core::mem::MaybeUninit::<$parent>::uninit();
+        let ptr = parent.as_ptr();
+
+        #[allow(unused_unsafe)]
+        unsafe {
+            let field = $crate::ptr_project!(const ptr $field);
+            field.cast::<u8>().offset_from(ptr.cast::<u8>()) as usize
+        }
+    }};
+}
+
 #[doc(hidden)]
 pub mod derive {
     pub use core::iter::{once, Once};
-    use core::marker::PhantomData;
+    use core::{any::Any, marker::PhantomData};
 
     pub struct Invariant<T: ?Sized>(PhantomData<fn() -> *mut T>);
 
@@ -53,6 +111,142 @@ pub mod derive {
     }
     impl<T: ?Sized> Copy for Invariant<T> {
     }
+
+    pub fn no_fields() -> &'static [crate::FieldInfo] {
+        &[]
+    }
+
+    // Dev Note: this uses the same auto-ref specialization as `DebugProbe`
+    pub struct ReflectProbe<T>(pub PhantomData<T>);
+
+    pub trait ViaReflect {
+        fn fields(&self) -> &'static [crate::FieldInfo];
+    }
+
+    impl<T: crate::Reflect> ViaReflect for ReflectProbe<T> {
+        fn fields(&self) -> &'static [crate::FieldInfo] {
+            T::FIELD_INFO
+        }
+    }
+
+    pub trait ViaNoFields {
+        fn fields(&self) -> &'static [crate::FieldInfo] {
+            &[]
+        }
+    }
+
+    impl<T> ViaNoFields for &ReflectProbe<T> {
+    }
+
+    pub unsafe fn as_any<T: 'static>(ptr: *const u8) -> *const dyn Any {
+        ptr.cast::<T>()
+    }
+
+    pub unsafe fn as_any_mut<T: 'static>(ptr: *mut u8) -> *mut dyn Any {
+        ptr.cast::<T>()
+    }
+
+    // Dev Note: this uses auto-ref specialization, `DebugProbe<T>` only
+    // implements `ViaDebug` if `T: Debug`, otherwise method resolution falls
+    // back to `ViaNothing` on `&DebugProbe<T>`. This must be called on a
+    // concrete type, so it is only used in the code generated by the derive.
+    pub struct DebugProbe<T>(pub *const T);
+
+    pub trait ViaDebug {
+        unsafe fn visit_debug(
+            &self,
+            visitor: &mut dyn FnMut(&dyn core::fmt::Debug),
+        );
+    }
+
+    impl<T: core::fmt::Debug> ViaDebug for DebugProbe<T> {
+        unsafe fn visit_debug(
+            &self,
+            visitor: &mut dyn FnMut(&dyn core::fmt::Debug),
+        ) {
+            visitor(&*self.0)
+        }
+    }
+
+    pub trait ViaNothing {
+        unsafe fn visit_debug(
+            &self,
+            _: &mut dyn FnMut(&dyn core::fmt::Debug),
+        ) {
+        }
+    }
+
+    impl<T> ViaNothing for &DebugProbe<T> {
+    }
+
+    /// A field that can be created out of thin air, this is used as the prefix
+    /// of nested field types
+    pub trait Prefix: crate::Field {
+        const INIT: Self;
+    }
+
+    impl<T> Prefix for crate::Identity<T> {
+        const INIT: Self = Self::NEW;
+    }
+
+    /// The field types of a type, nested under the prefix `P`
+    ///
+    /// # Safety
+    ///
+    /// `Fields` must be a zero-sized type, and `FIELDS` must be its only value
+    pub unsafe trait Nested<P> {
+        type Fields;
+
+        const FIELDS: Self::Fields;
+    }
+
+    /// Get the nested field types from a reference to the prefix, this allows
+    /// derived field types to `Deref` to their child fields
+    pub fn nested<T: Nested<P>, P>(_: &P) -> &T::Fields {
+        let () = ZeroSized::<T::Fields>::ASSERT;
+
+        // Safety
+        //
+        // `T::Fields` is a zero-sized type which only has the value `FIELDS`,
+        // so any non-null and aligned pointer is a valid reference to it
+        unsafe { &*core::ptr::NonNull::dangling().as_ptr() }
+    }
+
+    struct ZeroSized<T>(PhantomData<T>);
+
+    impl<T> ZeroSized<T> {
+        /// Fails to compile if `T` isn't zero-sized
+        const ASSERT: () = assert!(
+            core::mem::size_of::<T>() == 0,
+            "`Nested::Fields` must be zero-sized"
+        );
+    }
+
+    /// Used by the derive to bound the pinned fields of a type, without the
+    /// bounds being trivial
+    pub struct Pinned<'a, T: ?Sized>(PhantomData<&'a ()>, T);
+
+    /// A single character of a field name
+    pub struct Char<const C: char>;
+
+    /// The field of a type with the name `N`, nested under the prefix `P`
+    ///
+    /// Names are tuples of `Char`s, which are generated by `field_name!`
+    pub trait Child<N, P> {
+        type Field: Prefix;
+    }
+
+    /// A single step of a field path
+    pub trait Step<N>: Prefix {
+        type Next: Prefix;
+    }
+
+    impl<N, P: Prefix> Step<N> for P
+    where
+        P::Type: Child<N, P>,
+    {
+        type Next = <P::Type as Child<N, P>>::Field;
+    }
 }
 
 // Dev Note: we use `fn() -> T` so that we are covariant and non-owning in `T`,
@@ -307,6 +501,14 @@ pub trait ProjectAll<Parent, F> {
 ///         ),
 ///         Tap::FIELDS.val,
 ///     );
+///
+///     // or with nested fields, which have a simpler type
+///
+///     let foo_to_val = Foo::fields().bar.tap.val;
+///
+///     use gfp_core::{field_path, FieldPath};
+///
+///     const FOO_TO_VAL_PATH: FieldPath!(Foo.bar.tap.val) = field_path!(Foo.bar.tap.val);
 /// }
 /// # }
 /// ```
@@ -405,6 +607,9 @@ pub unsafe trait Field {
         &self,
         ptr: *const Self::Type,
     ) -> *const Self::Parent {
+        #[cfg(feature = "checked")]
+        check::assert_field(self);
+
         // Safety
         // * `ptr` is guaranteed to be a pointer to a field of `Parent`
         // * `field_offset` is guarateed to give the correct offset of the field
@@ -437,6 +642,9 @@ pub unsafe trait Field {
         &self,
         ptr: *mut Self::Type,
     ) -> *mut Self::Parent {
+        #[cfg(feature = "checked")]
+        check::assert_field(self);
+
         // Safety
         // * `ptr` is guaranteed to be a pointer to a field of `Parent`
         // * `field_offset` is guarateed to give the correct offset of the field
@@ -502,6 +710,92 @@ pub unsafe trait Field {
     {
         Chain::new(self, f)
     }
+
+    /// Select a range of bits inside of an integer `Field`
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty, or if it doesn't fit inside of `Type`
+    fn bits(self, bits: Range<u32>) -> Bits<Self>
+    where
+        Self: Sized,
+        Self::Type: BitInt,
+    {
+        Bits::new(self, bits)
+    }
+
+    /// View the bytes of this field inside of `parent`
+    fn bytes<'a>(&self, parent: &'a Self::Parent) -> &'a [u8]
+    where
+        Self::Type: AsBytes,
+    {
+        let () = <Self::Type as AsBytes>::NO_PADDING;
+
+        unsafe {
+            let field = self.project_raw(parent).cast::<u8>();
+            let len = core::mem::size_of::<Self::Type>();
+            core::slice::from_raw_parts(field, len)
+        }
+    }
+
+    /// Mutably view the bytes of this field inside of `parent`
+    fn bytes_mut<'a>(&self, parent: &'a mut Self::Parent) -> &'a mut [u8]
+    where
+        Self::Type: Pod + AsBytes,
+    {
+        let () = <Self::Type as AsBytes>::NO_PADDING;
+
+        unsafe {
+            let field = self.project_raw_mut(parent).cast::<u8>();
+            let len = core::mem::size_of::<Self::Type>();
+            core::slice::from_raw_parts_mut(field, len)
+        }
+    }
+
+    /// Copy this field from `src` to `dst`
+    fn copy_from(&self, dst: &mut Self::Parent, src: &Self::Parent)
+    where
+        Self::Type: Copy,
+    {
+        unsafe { *self.project_raw_mut(dst) = *self.project_raw(src) }
+    }
+
+    /// Clone this field from `src` into `dst`, this reuses the resources of
+    /// the field in `dst` like `Clone::clone_from`
+    fn clone_field_from(&self, dst: &mut Self::Parent, src: &Self::Parent)
+    where
+        Self::Type: Clone,
+    {
+        unsafe {
+            let dst = &mut *self.project_raw_mut(dst);
+            dst.clone_from(&*self.project_raw(src))
+        }
+    }
+
+    /// Replace this field in `parent` with `value`, and return the old value
+    fn replace(
+        &self,
+        parent: &mut Self::Parent,
+        value: Self::Type,
+    ) -> Self::Type {
+        unsafe { core::mem::replace(&mut *self.project_raw_mut(parent), value) }
+    }
+
+    /// Take this field out of `parent`, and leave the default value in its
+    /// place
+    fn take(&self, parent: &mut Self::Parent) -> Self::Type
+    where
+        Self::Type: Default,
+    {
+        self.replace(parent, Self::Type::default())
+    }
+
+    /// Swap this field between `a` and `b`
+    fn swap(&self, a: &mut Self::Parent, b: &mut Self::Parent) {
+        unsafe {
+            core::ptr::swap(self.project_raw_mut(a), self.project_raw_mut(b))
+        }
+    }
 }
 
 unsafe impl<F: ?Sized + Field> Field for &F {
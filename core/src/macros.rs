@@ -20,7 +20,8 @@ pub use core::iter::{once, Once};
 ///
 /// # Deprecated
 ///
-/// Please use the `#[derive(Field)]` macro instead
+/// Please use the `#[derive(Field)]` macro instead, or `remote_fields!` for
+/// types from other crates
 #[deprecated]
 #[macro_export]
 macro_rules! field {
@@ -57,3 +58,146 @@ macro_rules! field {
         }
     };
 }
+
+/// Create the field types of a struct from another crate
+///
+/// `#[derive(Field)]` only works on types in the current crate, this macro
+/// mirrors the public fields of a foreign struct instead. It generates a
+/// module with a field type for each field, a `Fields` struct that holds all
+/// of them, and a `fields` function to get the `Fields` struct.
+///
+/// The names and types of the fields are checked against the struct, and every
+/// field must be listed unless the list ends with `..`
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use core::ops::Range;
+/// use gfp_core::ProjectTo;
+///
+/// gfp_core::remote_fields! {
+///     mod range_fields for Range<u32> {
+///         start: u32,
+///         end: u32,
+///     }
+/// }
+///
+/// fn main() {
+///     let fields = range_fields::fields();
+///     let mut range = 0..10;
+///
+///     *(&mut range).project_to(fields.start) = 5;
+///
+///     assert_eq!(range, 5..10);
+/// }
+/// # }
+/// ```
+///
+/// Missing fields or mismatched types fail to compile
+///
+/// ```compile_fail
+/// # #![feature(raw_ref_op)]
+/// use core::ops::Range;
+///
+/// gfp_core::remote_fields! {
+///     mod range_fields for Range<u32> {
+///         start: u64,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! remote_fields {
+    (
+        $(#[$meta:meta])*
+        $vis:vis mod $module:ident for $parent:ty {
+            $($field:ident: $field_ty:ty,)*
+            ..
+        }
+    ) => {
+        $crate::remote_fields! {
+            @module($(#[$meta])*) $vis $module $parent { .. }
+            $($field: $field_ty),*
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis mod $module:ident for $parent:ty {
+            $($field:ident: $field_ty:ty),* $(,)?
+        }
+    ) => {
+        $crate::remote_fields! {
+            @module($(#[$meta])*) $vis $module $parent {}
+            $($field: $field_ty),*
+        }
+    };
+    (
+        @module($($attr:tt)*) $vis:vis $module:ident $parent:ty
+        { $($rest:tt)* }
+        $($field:ident: $field_ty:ty),*
+    ) => {
+        $($attr)*
+        #[allow(non_snake_case)]
+        $vis mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            type Parent = $parent;
+
+            $(
+                #[doc = concat!("The field type of `", stringify!($field), "`")]
+                #[allow(non_camel_case_types)]
+                #[derive(Clone, Copy)]
+                pub struct $field;
+
+                unsafe impl $crate::Field for $field {
+                    type Parent = Parent;
+                    type Type = $field_ty;
+
+                    #[inline]
+                    unsafe fn project_raw(
+                        &self,
+                        ptr: *const Self::Parent,
+                    ) -> *const Self::Type {
+                        &raw const (*ptr).$field
+                    }
+
+                    #[inline]
+                    unsafe fn project_raw_mut(
+                        &self,
+                        ptr: *mut Self::Parent,
+                    ) -> *mut Self::Type {
+                        &raw mut (*ptr).$field
+                    }
+                }
+            )*
+
+            /// All of the field types
+            #[derive(Clone, Copy)]
+            pub struct Fields {
+                $(
+                    #[doc = concat!("The field `", stringify!($field), "`")]
+                    pub $field: $field,
+                )*
+            }
+
+            /// All of the field types
+            pub const FIELDS: Fields = Fields {
+                $($field,)*
+            };
+
+            /// Get all of the field types
+            pub fn fields() -> Fields {
+                FIELDS
+            }
+
+            // checks that every field was listed
+            #[allow(dead_code)]
+            fn exhaustive(parent: &Parent) {
+                let Parent {
+                    $($field: _,)*
+                    $($rest)*
+                } = parent;
+            }
+        }
+    };
+}
@@ -0,0 +1,287 @@
+use crate::{
+    project::from_mut::PtrToRefMut,
+    type_list::{
+        FieldList, Pluck, ProjectRawMut, ProjectedMut, SameRanges, Sculpt,
+    },
+    Disjoint, Field,
+};
+
+use core::marker::PhantomData;
+use typsy::map::{Map, Mapped};
+
+/// A unique borrow of `Parent` that only gives access to the fields in `L`
+///
+/// This can be passed around in place of a `&mut Parent`, so that a function
+/// can only touch the fields it was given. Fields are found by their type,
+/// so using a field that is not in `L` fails to compile. A `Partial` can be
+/// split into smaller `Partial`s with no fields in common, which can then be
+/// used at the same time, even on different threads.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Disjoint, Field, FieldPath, Partial};
+/// use typsy::{
+///     hlist,
+///     hlist::{Cons, Nil},
+/// };
+///
+/// #[derive(Field)]
+/// struct Game {
+///     score:    u32,
+///     position: f32,
+///     velocity: f32,
+/// }
+///
+/// type Physics =
+///     Cons<FieldPath!(Game.position), Cons<FieldPath!(Game.velocity), Nil>>;
+///
+/// fn physics(mut game: Partial<'_, Game, Physics>) {
+///     let fields = Game::fields();
+///     let velocity = *game.get(fields.velocity);
+///     *game.get_mut(fields.position) += velocity;
+/// }
+///
+/// fn main() {
+///     let fields = Game::fields();
+///     let mut game = Game {
+///         score:    0,
+///         position: 1.0,
+///         velocity: 2.0,
+///     };
+///
+///     let all = hlist!(fields.score, fields.position, fields.velocity);
+///     let partial = Partial::new(&mut game, Disjoint::new(all));
+///
+///     let (physics_fields, mut rest) =
+///         partial.split(hlist!(fields.position, fields.velocity));
+///     physics(physics_fields);
+///     *rest.get_mut(fields.score) += 1;
+///
+///     assert_eq!(game.score, 1);
+///     assert_eq!(game.position, 3.0);
+/// }
+/// # }
+/// ```
+///
+/// A `Partial` can only be sent to another thread if all of its fields can
+/// be, even if `Parent` can be sent
+///
+/// ```compile_fail
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Disjoint, Field, Partial};
+/// use std::rc::Rc;
+///
+/// #[derive(Field)]
+/// struct Shares {
+///     a: Rc<u32>,
+///     b: Rc<u32>,
+/// }
+///
+/// // `a` and `b` are always dropped together on the same thread
+/// unsafe impl Send for Shares {}
+///
+/// fn main() {
+///     let fields = Shares::fields();
+///     let rc = Rc::new(0);
+///     let mut shares = Shares {
+///         a: rc.clone(),
+///         b: rc,
+///     };
+///
+///     let list = Disjoint::new(typsy::hlist!(fields.a, fields.b));
+///     let partial = Partial::new(&mut shares, list);
+///     let (mut a, _b) = partial.split(typsy::hlist!(fields.a));
+///
+///     std::thread::scope(|scope| {
+///         scope.spawn(move || a.get_mut(fields.a).clone());
+///     });
+/// }
+/// # }
+/// ```
+pub struct Partial<'a, Parent, L> {
+    ptr:    *mut Parent,
+    fields: L,
+    mark:   PhantomData<&'a mut Parent>,
+}
+
+// Safety: a `Partial` acts like the references to its fields, which can be
+// split off and sent to other threads on their own, so every one of them must
+// be `Send` instead of `Parent`
+unsafe impl<'a, Parent, L> Send for Partial<'a, Parent, L>
+where
+    L: FieldList<Parent> + Send,
+    ProjectedMut<Parent, L>: Map<PtrToRefMut<'a>>,
+    Mapped<ProjectedMut<Parent, L>, PtrToRefMut<'a>>: Send,
+{
+}
+unsafe impl<'a, Parent, L> Sync for Partial<'a, Parent, L>
+where
+    L: FieldList<Parent> + Sync,
+    ProjectedMut<Parent, L>: Map<PtrToRefMut<'a>>,
+    Mapped<ProjectedMut<Parent, L>, PtrToRefMut<'a>>: Sync,
+{
+}
+
+impl<'a, Parent, L> Partial<'a, Parent, L> {
+    /// Borrow the fields `L` of `parent`
+    pub fn new(parent: &'a mut Parent, fields: Disjoint<L>) -> Self
+    where
+        L: FieldList<Parent>,
+    {
+        Self {
+            ptr:    parent,
+            fields: fields.into_inner(),
+            mark:   PhantomData,
+        }
+    }
+
+    /// The fields that can be accessed
+    pub fn fields(&self) -> &L {
+        &self.fields
+    }
+
+    /// Reborrow this `Partial` for a shorter lifetime
+    pub fn reborrow(&mut self) -> Partial<'_, Parent, L>
+    where
+        L: Copy,
+    {
+        Partial {
+            ptr:    self.ptr,
+            fields: self.fields,
+            mark:   PhantomData,
+        }
+    }
+
+    /// Get a shared reference to the field
+    ///
+    /// The field is found by its type, the copy of the field that is stored
+    /// in the `Partial` is used to project
+    ///
+    /// # Panics
+    ///
+    /// If `field` doesn't cover the same bytes as the stored field, this can
+    /// only happen with fields like `Dynamic` where many fields share a type
+    #[track_caller]
+    pub fn get<F, I>(&self, field: F) -> &F::Type
+    where
+        F: Field<Parent = Parent>,
+        L: Copy + Pluck<F, I>,
+    {
+        let field = Self::stored(self.fields, field);
+
+        // Safety: `ptr` came from a `&mut Parent` which lives for `'a`, and
+        // only the fields in the list can be accessed through it
+        unsafe { &*field.project_raw(self.ptr) }
+    }
+
+    /// Get a unique reference to the field
+    ///
+    /// The field is found by its type, the copy of the field that is stored
+    /// in the `Partial` is used to project
+    ///
+    /// # Panics
+    ///
+    /// If `field` doesn't cover the same bytes as the stored field, see `get`
+    #[track_caller]
+    pub fn get_mut<F, I>(&mut self, field: F) -> &mut F::Type
+    where
+        F: Field<Parent = Parent>,
+        L: Copy + Pluck<F, I>,
+    {
+        let field = Self::stored(self.fields, field);
+
+        // Safety: see `get`, the field is borrowed from `self` so there can't
+        // be any other references to it
+        unsafe { &mut *field.project_raw_mut(self.ptr) }
+    }
+
+    /// Turn this `Partial` into a reference to one of its fields
+    ///
+    /// # Panics
+    ///
+    /// If `field` doesn't cover the same bytes as the stored field, see `get`
+    #[track_caller]
+    pub fn project_to<F, I>(self, field: F) -> &'a mut F::Type
+    where
+        F: Field<Parent = Parent>,
+        L: Pluck<F, I>,
+    {
+        let field = Self::stored(self.fields, field);
+
+        // Safety: see `get`, `self` is consumed so there can't be any other
+        // references to the field
+        unsafe { &mut *field.project_raw_mut(self.ptr) }
+    }
+
+    #[track_caller]
+    fn stored<F, I>(fields: L, field: F) -> F
+    where
+        F: Field<Parent = Parent>,
+        L: Pluck<F, I>,
+    {
+        let (stored, _) = fields.pluck();
+        assert_eq!(
+            field.range(),
+            stored.range(),
+            "The field is not the one in the `Partial`"
+        );
+        stored
+    }
+
+    /// Turn this `Partial` into references to all of its fields
+    pub fn project_all(self) -> Mapped<ProjectedMut<Parent, L>, PtrToRefMut<'a>>
+    where
+        L: FieldList<Parent>,
+        ProjectedMut<Parent, L>: Map<PtrToRefMut<'a>>,
+    {
+        // Safety: the fields were checked for overlap when this `Partial` was
+        // created, and `self` is consumed
+        unsafe {
+            self.fields
+                .map(ProjectRawMut::new(self.ptr))
+                .map(PtrToRefMut(PhantomData))
+        }
+    }
+
+    /// Split off the fields in `fields` into a new `Partial`, and keep the
+    /// rest of the fields in the other one
+    ///
+    /// The fields are found by their type, so they may be in any order
+    ///
+    /// # Panics
+    ///
+    /// If any field in `fields` doesn't cover the same bytes as the stored
+    /// field that was found, see `get`
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn split<S, I>(
+        self,
+        fields: S,
+    ) -> (Partial<'a, Parent, S>, Partial<'a, Parent, L::Remainder>)
+    where
+        L: Sculpt<S, I>,
+        S: SameRanges,
+    {
+        let (stored, rest) = self.fields.sculpt();
+        assert!(
+            fields.same_ranges(&stored),
+            "The fields are not the ones in the `Partial`"
+        );
+
+        // the stored fields are used in both halves, so they stay disjoint
+        (
+            Partial {
+                ptr:    self.ptr,
+                fields: stored,
+                mark:   PhantomData,
+            },
+            Partial {
+                ptr:    self.ptr,
+                fields: rest,
+                mark:   PhantomData,
+            },
+        )
+    }
+}
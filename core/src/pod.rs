@@ -0,0 +1,142 @@
+use core::marker::PhantomData;
+
+/// A plain-old-data type, which is valid for any initialized bit pattern
+///
+/// This can be implemented for your own types with `#[field(pod)]`
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod __ {
+/// use gfp_core::Field;
+///
+/// #[derive(Clone, Copy, Field)]
+/// #[field(pod)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+/// # }
+/// ```
+///
+/// The fields of a `Pod` are projected in place, so packed types can't be
+/// `Pod`, since their fields may be misaligned
+///
+/// ```compile_fail
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{ByteView, Field, ProjectTo};
+///
+/// #[derive(Clone, Copy, Field)]
+/// #[field(pod)]
+/// #[repr(C, packed)]
+/// struct Packed {
+///     a: u8,
+///     b: u32,
+/// }
+///
+/// fn main() {
+///     let bytes = [0; 5];
+///     let view = ByteView::<Packed>::new(&bytes).unwrap();
+///     let _: &u32 = view.project_to(Packed::fields().b);
+/// }
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// * any sequence of initialized bytes of length `size_of::<Self>()` must be
+///   a valid `Self`
+/// * `Self` must not contain any `UnsafeCell`s
+/// * every field of an aligned `Self` must be aligned, so `Self` can't be
+///   `repr(packed)`
+pub unsafe trait Pod: Copy {}
+
+/// A type without any padding bytes, which can always be viewed as bytes
+///
+/// This can be implemented for your own types with `#[field(as_bytes)]`,
+/// which also checks the fields for padding
+///
+/// ```compile_fail
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::Field;
+///
+/// #[derive(Field)]
+/// #[field(as_bytes)]
+/// #[repr(C)]
+/// struct Inner {
+///     flag: bool,
+///     len:  u16,
+/// }
+///
+/// // the size of `Outer` is the size of `Inner`, but `Inner` has padding
+/// #[derive(Field)]
+/// #[field(as_bytes)]
+/// #[repr(C)]
+/// struct Outer {
+///     inner: Inner,
+/// }
+///
+/// #[derive(Field)]
+/// struct Message {
+///     outer: Outer,
+/// }
+///
+/// fn main() {
+///     let message = Message {
+///         outer: Outer {
+///             inner: Inner {
+///                 flag: true,
+///                 len:  1,
+///             },
+///         },
+///     };
+///
+///     Message::fields().outer.bytes(&message);
+/// }
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// * every byte of any `Self` must be initialized, so `Self` can't contain
+///   any padding bytes
+/// * `Self` must not contain any `UnsafeCell`s
+/// * `NO_PADDING` must not be overridden, except by `#[field(as_bytes)]`
+pub unsafe trait AsBytes {
+    // Dev Note: the derive overrides this with an assertion that the type has
+    // no padding. Since it depends on generic parameters, it can only be
+    // checked after monomorphization, so it must be used by every function that
+    // relies on `AsBytes`
+    #[doc(hidden)]
+    const NO_PADDING: () = ();
+}
+
+macro_rules! pod {
+    ($($ty:ty),* $(,)?) => {$(
+        unsafe impl Pod for $ty {}
+        unsafe impl AsBytes for $ty {}
+    )*};
+}
+
+pod! {
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64, (),
+}
+
+unsafe impl AsBytes for bool {
+}
+unsafe impl AsBytes for char {
+}
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {
+}
+unsafe impl<T: AsBytes, const N: usize> AsBytes for [T; N] {
+    const NO_PADDING: () = T::NO_PADDING;
+}
+unsafe impl<T: ?Sized> Pod for PhantomData<T> {
+}
+unsafe impl<T: ?Sized> AsBytes for PhantomData<T> {
+}
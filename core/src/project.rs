@@ -1,3 +1,4 @@
+use type_list::Access;
 use typsy::cmp::Any;
 
 use super::*;
@@ -6,11 +7,13 @@ use super::*;
 pub mod from_arc;
 #[cfg(feature = "alloc")]
 pub mod from_box;
+pub mod from_byte_view;
 pub mod from_mut;
 pub mod from_pin;
 #[cfg(feature = "alloc")]
 pub mod from_rc;
 pub mod from_ref;
+pub mod from_relative;
 
 use core::{marker::PhantomData, ops::Deref, pin::Pin};
 
@@ -27,7 +30,7 @@ impl<F: Field, T: ProjectTo<F>> ProjectTo<F> for Option<T> {
     }
 }
 
-pub struct PtrToRef<'a>(PhantomData<&'a ()>);
+pub struct PtrToRef<'a>(pub(crate) PhantomData<&'a ()>);
 
 typsy::call! {
     fn['a, T: 'a + Sized](&mut self: PtrToRef<'a>, ptr: *const T) -> &'a T {
@@ -41,7 +44,7 @@ pub struct FindOverlap<S> {
 }
 
 impl<S> FindOverlap<S> {
-    fn new(set: S) -> Self {
+    pub(crate) fn new(set: S) -> Self {
         FindOverlap {
             set,
             counter: 0,
@@ -59,7 +62,7 @@ typsy::call! {
     fn[S, F](&mut self: FindOverlap<S>, field: F) -> bool
     where(
         S: Copy + for<'b> Any<'b, FindOverlapInner<F>>,
-        F: Field,
+        F: Access,
     ){
         self.counter += 1;
 
@@ -70,22 +73,37 @@ typsy::call! {
         })
     }
 
-    fn[I: Field, J: Field](&mut self: FindOverlapInner<I>, input: J) -> bool {
+    fn[I: Access, J: Access](
+        &mut self: FindOverlapInner<I>,
+        input: J
+    ) -> bool {
         self.counter += 1;
 
-        if self.id <= self.counter {
+        // shared fields may overlap each other
+        if self.id <= self.counter || !(I::UNIQUE || J::UNIQUE) {
             return false
         }
 
-        let field = self.field.range();
-        let input = input.range();
+        is_overlapping_entry(&self.field, &input)
+    }
+}
+
+/// Checks if two entries of a field list overlap, bit-fields of the same
+/// integer field only overlap if their bits do
+pub(crate) fn is_overlapping_entry<I: Access, J: Access>(a: &I, b: &J) -> bool {
+    let range = a.range();
 
-        is_overlapping(field, input)
+    if range == b.range() {
+        if let (Some(a), Some(b)) = (a.bit_range(), b.bit_range()) {
+            return a.start < b.end && b.start < a.end
+        }
     }
+
+    is_overlapping(range, b.range())
 }
 
 #[allow(clippy::suspicious_operation_groupings)]
-fn is_overlapping(a: Range<usize>, b: Range<usize>) -> bool {
+pub(crate) fn is_overlapping(a: Range<usize>, b: Range<usize>) -> bool {
     !b.is_empty()
         && !a.is_empty()
         && (a.contains(&b.start) || b.contains(&a.start))
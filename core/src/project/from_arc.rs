@@ -95,3 +95,14 @@ impl<'a, Parent, F: FieldList<Parent>> ProjectAll<Parent, F> for Arc<Parent> {
         }
     }
 }
+
+impl<Parent, F: FieldList<Parent>> ProjectAll<Parent, Disjoint<F>>
+    for Arc<Parent>
+{
+    type Projection = ProjectedArcSet<Parent, Projected<Parent, F>>;
+
+    #[inline]
+    fn project_all(self, field: Disjoint<F>) -> Self::Projection {
+        self.project_all(field.into_inner())
+    }
+}
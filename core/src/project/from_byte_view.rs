@@ -0,0 +1,40 @@
+use super::*;
+
+use crate::{ByteView, Pod};
+use type_list::{FieldList, ProjectRaw, Projected};
+use typsy::map::{Map, Mapped};
+
+impl<'a, F: Field> ProjectTo<F> for ByteView<'a, F::Parent>
+where
+    F::Parent: 'a + Pod,
+    F::Type: 'a,
+{
+    type Projection = &'a F::Type;
+
+    fn project_to(self, field: F) -> Self::Projection {
+        let bytes = &self.bytes()[field.range()];
+
+        // Safety
+        // * `ByteView::new` checked that the buffer holds a properly aligned
+        //      `Parent`, and since `Parent: Pod` any bytes are a valid `Parent`
+        // * the fields of a valid `Parent` are valid values of their type
+        unsafe { &*bytes.as_ptr().cast() }
+    }
+}
+
+impl<'a, Parent: Pod, F: FieldList<Parent>> ProjectAll<Parent, F>
+    for ByteView<'a, Parent>
+where
+    Projected<Parent, F>: Map<PtrToRef<'a>>,
+{
+    type Projection = Mapped<Projected<Parent, F>, PtrToRef<'a>>;
+
+    #[inline]
+    fn project_all(self, field: F) -> Self::Projection {
+        unsafe {
+            field
+                .map(ProjectRaw::new(self.as_ptr()))
+                .map(PtrToRef(PhantomData))
+        }
+    }
+}
@@ -5,12 +5,16 @@ use typsy::{
     map::{Map, Mapped},
 };
 
-pub struct PtrToRefMut<'a>(PhantomData<&'a ()>);
+pub struct PtrToRefMut<'a>(pub(crate) PhantomData<&'a ()>);
 
 typsy::call! {
     fn['a, T: 'a](&mut self: PtrToRefMut<'a>, ptr: *mut T) -> &'a mut T {
         unsafe { &mut *ptr }
     }
+
+    fn['a, T: 'a](&mut self: PtrToRefMut<'a>, ptr: *const T) -> &'a T {
+        unsafe { &*ptr }
+    }
 }
 
 unsafe impl<T: ?Sized> PinnablePointer for &mut T {
@@ -50,3 +54,22 @@ where
         }
     }
 }
+
+impl<'a, F, Parent> ProjectAll<Parent, Disjoint<F>> for &'a mut Parent
+where
+    F: FieldList<Parent>,
+    ProjectedMut<Parent, F>: Map<PtrToRefMut<'a>>,
+{
+    type Projection = Mapped<ProjectedMut<Parent, F>, PtrToRefMut<'a>>;
+
+    /// projects to the given field, the fields were already checked for
+    /// overlap when the `Disjoint` list was created
+    fn project_all(self, field: Disjoint<F>) -> Self::Projection {
+        unsafe {
+            field
+                .into_inner()
+                .map(ProjectRawMut::new(self))
+                .map(PtrToRefMut(PhantomData))
+        }
+    }
+}
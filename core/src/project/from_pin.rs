@@ -88,3 +88,26 @@ where
         }
     }
 }
+
+impl<Parent, F: Copy + FieldList<Parent>, P> ProjectAll<Parent, Disjoint<F>>
+    for Pin<P>
+where
+    P: PinnablePointer + ProjectAll<Parent, Disjoint<F>>,
+    F: Map<CreateTag>,
+    Mapped<F, CreateTag>: Zip<P::Projection>,
+    Zipped<Mapped<F, CreateTag>, P::Projection>: Map<BuildOutput>,
+{
+    type Projection =
+        Mapped<Zipped<Mapped<F, CreateTag>, P::Projection>, BuildOutput>;
+
+    #[inline]
+    fn project_all(self, field: Disjoint<F>) -> Self::Projection {
+        unsafe {
+            let tags = field.into_inner().map(CreateTag);
+
+            let raw_output = Pin::into_inner_unchecked(self).project_all(field);
+
+            tags.zip(raw_output).map(BuildOutput)
+        }
+    }
+}
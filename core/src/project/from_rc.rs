@@ -81,3 +81,14 @@ impl<'a, Parent, F: FieldList<Parent>> ProjectAll<Parent, F> for Rc<Parent> {
         }
     }
 }
+
+impl<Parent, F: FieldList<Parent>> ProjectAll<Parent, Disjoint<F>>
+    for Rc<Parent>
+{
+    type Projection = ProjectedRcSet<Parent, Projected<Parent, F>>;
+
+    #[inline]
+    fn project_all(self, field: Disjoint<F>) -> Self::Projection {
+        self.project_all(field.into_inner())
+    }
+}
@@ -29,3 +29,17 @@ where
         unsafe { field.map(ProjectRaw::new(self)).map(PtrToRef(PhantomData)) }
     }
 }
+
+impl<'a, Parent, F: FieldList<Parent>> ProjectAll<Parent, Disjoint<F>>
+    for &'a Parent
+where
+    Parent: 'a,
+    Projected<Parent, F>: Map<PtrToRef<'a>>,
+{
+    type Projection = Mapped<Projected<Parent, F>, PtrToRef<'a>>;
+
+    #[inline]
+    fn project_all(self, field: Disjoint<F>) -> Self::Projection {
+        self.project_all(field.into_inner())
+    }
+}
@@ -0,0 +1,33 @@
+use super::*;
+
+use crate::{RelOffset, RelPtr};
+
+impl<F: Field> ProjectTo<F> for RelPtr<F::Parent> {
+    type Projection = RelOffset<F::Type>;
+
+    fn project_to(self, field: F) -> Self::Projection {
+        RelOffset::<F::Parent>::from_offset(self.offset()).project_to(field)
+    }
+}
+
+impl<F: Field> ProjectTo<F> for RelOffset<F::Parent> {
+    type Projection = RelOffset<F::Type>;
+
+    fn project_to(self, field: F) -> Self::Projection {
+        if self.is_null() {
+            RelOffset::NULL
+        } else {
+            let offset = field.field_offset() as isize;
+            let offset = self.offset().wrapping_add(offset);
+
+            // an offset of zero is null, so a pointer to the `RelPtr` itself
+            // can't be represented
+            assert!(
+                offset != 0,
+                "Projected a `RelPtr` to the location it is relative to"
+            );
+
+            RelOffset::from_offset(offset)
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Runtime reflection over the fields of a type
+
+use crate::{Dynamic, TypeLayout};
+
+use core::{
+    any::{type_name, Any, TypeId},
+    fmt,
+    ops::Range,
+};
+
+pub(crate) type AsAny = unsafe fn(*const u8) -> *const dyn Any;
+pub(crate) type AsAnyMut = unsafe fn(*mut u8) -> *mut dyn Any;
+type VisitDebug = unsafe fn(*const u8, &mut dyn FnMut(&dyn fmt::Debug));
+
+/// A runtime description of a single field, generated by `#[derive(Field)]`
+///
+/// See [`Reflect`] for how to get these
+#[derive(Clone, Copy)]
+pub struct FieldInfo {
+    name:                 &'static str,
+    offset:               usize,
+    size:                 usize,
+    align:                usize,
+    pub(crate) type_name: fn() -> &'static str,
+    type_id:              Option<fn() -> TypeId>,
+    fields:               fn() -> &'static [FieldInfo],
+    any:                  Option<(AsAny, AsAnyMut)>,
+    debug:                Option<VisitDebug>,
+}
+
+impl FieldInfo {
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    pub const unsafe fn new(
+        name: &'static str,
+        offset: usize,
+        size: usize,
+        align: usize,
+        type_name: fn() -> &'static str,
+        type_id: Option<fn() -> TypeId>,
+        fields: fn() -> &'static [FieldInfo],
+        any: Option<(AsAny, AsAnyMut)>,
+        debug: Option<VisitDebug>,
+    ) -> Self {
+        Self {
+            name,
+            offset,
+            size,
+            align,
+            type_name,
+            type_id,
+            fields,
+            any,
+            debug,
+        }
+    }
+
+    /// The name of the field, tuple fields are named by their index
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The offset of the field in bytes from the start of the parent
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The size of the field in bytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment of the field in bytes
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    /// The range of bytes covered by the field
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.size
+    }
+
+    /// The name of the type of the field, as given by
+    /// [`core::any::type_name`]
+    pub fn type_name(&self) -> &'static str {
+        (self.type_name)()
+    }
+
+    /// The `TypeId` of the type of the field
+    ///
+    /// This is only available if the parent has no generic parameters, since
+    /// then every field is `'static`, and is never available for union fields
+    pub fn type_id(&self) -> Option<TypeId> {
+        self.type_id.map(|type_id| type_id())
+    }
+
+    /// Checks if the field is of type `T`
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id() == Some(TypeId::of::<T>())
+    }
+
+    /// The fields of the type of the field, this is empty unless the type
+    /// implements [`Reflect`]
+    pub fn fields(&self) -> &'static [FieldInfo] {
+        (self.fields)()
+    }
+}
+
+impl PartialEq for FieldInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.offset == other.offset
+            && self.size == other.size
+            && self.align == other.align
+            && self.type_id() == other.type_id()
+    }
+}
+
+impl Eq for FieldInfo {
+}
+
+impl fmt::Debug for FieldInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldInfo")
+            .field("name", &self.name)
+            .field("offset", &self.offset)
+            .field("size", &self.size)
+            .field("align", &self.align)
+            .field("type_name", &self.type_name())
+            .finish()
+    }
+}
+
+/// A type with a runtime table of its fields, implemented by
+/// `#[derive(Field)]`
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Field, Reflect};
+///
+/// #[derive(Field)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// fn main() {
+///     let point = Point { x: 1, y: 2 };
+///     let mut names = Vec::new();
+///
+///     point.visit_debug(|info, value| {
+///         names.push(format!("{} = {:?}", info.name(), value))
+///     });
+///
+///     assert_eq!(names, ["x = 1", "y = 2"]);
+/// }
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// * `FIELD_INFO` must describe the fields of `Self` exactly
+pub unsafe trait Reflect: Sized {
+    /// The descriptions of every field, in declaration order
+    const FIELD_INFO: &'static [FieldInfo];
+
+    /// The descriptions of every field, in declaration order
+    fn field_info() -> &'static [FieldInfo] {
+        Self::FIELD_INFO
+    }
+
+    /// The layout of `Self`, with every field and padding gap
+    fn layout() -> TypeLayout {
+        TypeLayout::of::<Self>()
+    }
+
+    /// Find the description of a field by name
+    fn find_field(name: &str) -> Option<&'static FieldInfo> {
+        Self::FIELD_INFO.iter().find(|info| info.name == name)
+    }
+
+    /// Convert a field description into a `Dynamic` field
+    ///
+    /// This returns `None` if `info` is not a field of `Self`, or if the field
+    /// is not known to be a `T`
+    fn dynamic<T: 'static>(info: &FieldInfo) -> Option<Dynamic<Self, T>> {
+        if info.is::<T>() && Self::FIELD_INFO.contains(info) {
+            unsafe { Some(Dynamic::from_offset(info.offset)) }
+        } else {
+            None
+        }
+    }
+
+    /// Visit every `'static` field as `&dyn Any`
+    fn visit_any<V>(&self, mut visitor: V)
+    where
+        V: FnMut(&'static FieldInfo, &dyn Any),
+    {
+        let ptr = (self as *const Self).cast::<u8>();
+
+        for info in Self::FIELD_INFO {
+            if let Some((as_any, _)) = info.any {
+                let field = ptr.wrapping_add(info.offset);
+                visitor(info, unsafe { &*as_any(field) })
+            }
+        }
+    }
+
+    /// Visit every `'static` field as `&mut dyn Any`
+    fn visit_any_mut<V>(&mut self, mut visitor: V)
+    where
+        V: FnMut(&'static FieldInfo, &mut dyn Any),
+    {
+        let ptr = (self as *mut Self).cast::<u8>();
+
+        for info in Self::FIELD_INFO {
+            if let Some((_, as_any_mut)) = info.any {
+                let field = ptr.wrapping_add(info.offset);
+                visitor(info, unsafe { &mut *as_any_mut(field) })
+            }
+        }
+    }
+
+    /// Visit every field that implements `Debug` as `&dyn Debug`
+    ///
+    /// Fields whose `Debug` impl depends on a generic parameter are skipped
+    fn visit_debug<V>(&self, mut visitor: V)
+    where
+        V: FnMut(&'static FieldInfo, &dyn fmt::Debug),
+    {
+        let ptr = (self as *const Self).cast::<u8>();
+
+        for info in Self::FIELD_INFO {
+            if let Some(visit) = info.debug {
+                let field = ptr.wrapping_add(info.offset);
+                unsafe { visit(field, &mut |value| visitor(info, value)) }
+            }
+        }
+    }
+}
+
+/// The error returned when a path can't be resolved to a `Dynamic` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathError<'a> {
+    /// There is no field with the given name
+    UnknownField(&'a str),
+    /// The type of the field isn't known at runtime, because its parent has
+    /// generic parameters or is a union
+    Untyped(&'a str),
+    /// The field has a different type than the one requested
+    TypeMismatch {
+        /// The name of the field
+        field:    &'a str,
+        /// The requested type
+        expected: &'static str,
+        /// The type of the field
+        found:    &'static str,
+    },
+}
+
+impl fmt::Display for PathError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            PathError::UnknownField(field) => {
+                write!(f, "no field named `{}`", field)
+            },
+            PathError::Untyped(field) => {
+                write!(f, "the type of `{}` is not known", field)
+            },
+            PathError::TypeMismatch {
+                field,
+                expected,
+                found,
+            } => {
+                write!(
+                    f,
+                    "expected `{}` to be a `{}`, but found a `{}`",
+                    field, expected, found
+                )
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PathError<'_> {
+}
+
+/// A field found by name or by path
+pub(crate) struct Resolved<'a> {
+    pub name:   &'a str,
+    pub info:   &'static FieldInfo,
+    pub offset: usize,
+}
+
+impl<'a> Resolved<'a> {
+    pub fn from_name<P: Reflect>(name: &'a str) -> Result<Self, PathError<'a>> {
+        let info = P::find_field(name).ok_or(PathError::UnknownField(name))?;
+
+        Ok(Self {
+            name,
+            info,
+            offset: info.offset,
+        })
+    }
+
+    pub fn from_path<P: Reflect>(path: &'a str) -> Result<Self, PathError<'a>> {
+        let mut fields = P::FIELD_INFO;
+        let mut offset = 0;
+        let mut last = None;
+
+        for name in path.split('.') {
+            let info = fields
+                .iter()
+                .find(|info| info.name == name)
+                .ok_or(PathError::UnknownField(name))?;
+
+            offset += info.offset;
+            fields = info.fields();
+            last = Some((name, info));
+        }
+
+        // `split` always yields at least one name
+        let (name, info) = last.unwrap();
+
+        Ok(Self {
+            name,
+            info,
+            offset,
+        })
+    }
+
+    /// The type-erased accessors of the field, if it has a known type
+    pub fn any(&self) -> Result<(TypeId, AsAny, AsAnyMut), PathError<'a>> {
+        match (self.info.type_id(), self.info.any) {
+            (Some(type_id), Some((as_any, as_any_mut))) => {
+                Ok((type_id, as_any, as_any_mut))
+            },
+            _ => Err(PathError::Untyped(self.name)),
+        }
+    }
+
+    fn into_dynamic<P, T: 'static>(
+        self,
+    ) -> Result<Dynamic<P, T>, PathError<'a>> {
+        let (type_id, ..) = self.any()?;
+
+        if type_id == TypeId::of::<T>() {
+            unsafe { Ok(Dynamic::from_offset(self.offset)) }
+        } else {
+            Err(PathError::TypeMismatch {
+                field:    self.name,
+                expected: type_name::<T>(),
+                found:    self.info.type_name(),
+            })
+        }
+    }
+}
+
+impl<P: Reflect, T: 'static> Dynamic<P, T> {
+    /// Look up a field of `P` by name
+    pub fn from_name(name: &str) -> Result<Self, PathError<'_>> {
+        Resolved::from_name::<P>(name)?.into_dynamic()
+    }
+
+    /// Look up a field of `P` by a dotted path of names, like `"y.c.r"`,
+    /// each field along the path must implement [`Reflect`]
+    ///
+    /// ```rust
+    /// #![feature(raw_ref_op)]
+    /// # mod main {
+    /// use gfp_core::{Dynamic, Field, ProjectTo};
+    ///
+    /// #[derive(Field)]
+    /// struct Line {
+    ///     start: Point,
+    ///     end:   Point,
+    /// }
+    ///
+    /// #[derive(Field)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// fn main() {
+    ///     let line = Line {
+    ///         start: Point { x: 0, y: 1 },
+    ///         end:   Point { x: 2, y: 3 },
+    ///     };
+    ///
+    ///     let end_y = Dynamic::<Line, i32>::from_path("end.y").unwrap();
+    ///     assert_eq!(*line.project_to(end_y), 3);
+    ///
+    ///     assert!(Dynamic::<Line, u8>::from_path("end.y").is_err());
+    ///     assert!(Dynamic::<Line, i32>::from_path("end.z").is_err());
+    /// }
+    /// # }
+    /// ```
+    pub fn from_path(path: &str) -> Result<Self, PathError<'_>> {
+        Resolved::from_path::<P>(path)?.into_dynamic()
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{AsBytes, Pod};
+
+use core::marker::PhantomData;
+
+/// A pointer to a `T`, stored as a signed offset in bytes from the address of
+/// the `RelPtr` itself
+///
+/// Since the offset is relative, a `RelPtr` stays valid when the memory that
+/// holds both it and its target is moved as a whole, for example when it's
+/// memory-mapped from a file. An offset of zero represents a null pointer, so
+/// a `RelPtr` can't point to itself.
+///
+/// A `RelPtr` is only meaningful in place, a copy of it resolves from the
+/// address of the copy. Projecting a `RelPtr` to a `Field` yields a
+/// [`RelOffset`] to that field, which can't be resolved on its own, since it
+/// is relative to the address of the original `RelPtr`.
+///
+/// Projecting panics if the field is at the location that the `RelPtr` is
+/// relative to, for example when a node is projected to its own `RelPtr`
+/// field, because that would make it null.
+#[repr(transparent)]
+pub struct RelPtr<T> {
+    offset: isize,
+    _mark:  PhantomData<*const T>,
+}
+
+impl<T> Copy for RelPtr<T> {
+}
+impl<T> Clone for RelPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+unsafe impl<T> Pod for RelPtr<T> {
+}
+unsafe impl<T> AsBytes for RelPtr<T> {
+}
+
+impl<T> RelPtr<T> {
+    /// The null relative pointer
+    pub const NULL: Self = Self::from_offset(0);
+
+    /// Create a relative pointer from an offset in bytes
+    pub const fn from_offset(offset: isize) -> Self {
+        Self {
+            offset,
+            _mark: PhantomData,
+        }
+    }
+
+    /// Create a relative pointer which, when stored at `at`, points to
+    /// `target`
+    pub fn new(at: *const Self, target: *const T) -> Self {
+        let offset = (target as usize).wrapping_sub(at as usize);
+        Self::from_offset(offset as isize)
+    }
+
+    /// Get the offset in bytes
+    pub fn offset(&self) -> isize {
+        self.offset
+    }
+
+    /// Checks if this is the null relative pointer
+    pub fn is_null(&self) -> bool {
+        self.offset == 0
+    }
+
+    /// Point this relative pointer to `target`
+    pub fn set(&mut self, target: *const T) {
+        *self = Self::new(self, target)
+    }
+
+    /// Resolve this relative pointer as if it was stored at `at`, null
+    /// relative pointers resolve to null pointers
+    pub fn resolve_from(&self, at: *const Self) -> *const T {
+        if self.is_null() {
+            core::ptr::null()
+        } else {
+            at.cast::<u8>().wrapping_offset(self.offset).cast()
+        }
+    }
+
+    /// Resolve this relative pointer from its own address, so this must be
+    /// called on the `RelPtr` in place, not on a copy of it
+    pub fn as_ptr(&self) -> *const T {
+        self.resolve_from(self)
+    }
+
+    /// Get a reference to the target, or `None` if this is null
+    ///
+    /// # Safety
+    ///
+    /// If not null, the resolved pointer must point to a valid, initialized
+    /// `T` for the lifetime of `self`
+    pub unsafe fn as_ref(&self) -> Option<&T> {
+        self.as_ptr().as_ref()
+    }
+}
+
+/// The offset of a field of the target of a [`RelPtr`], relative to the
+/// address of that `RelPtr`
+///
+/// This is created by projecting a `RelPtr`, and can only be resolved from
+/// the address of the `RelPtr` that it was projected from.
+pub struct RelOffset<T> {
+    offset: isize,
+    _mark:  PhantomData<*const T>,
+}
+
+impl<T> Copy for RelOffset<T> {
+}
+impl<T> Clone for RelOffset<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> RelOffset<T> {
+    /// The null relative offset
+    pub const NULL: Self = Self::from_offset(0);
+
+    pub(crate) const fn from_offset(offset: isize) -> Self {
+        Self {
+            offset,
+            _mark: PhantomData,
+        }
+    }
+
+    /// Get the offset in bytes
+    pub fn offset(&self) -> isize {
+        self.offset
+    }
+
+    /// Checks if this was projected from the null relative pointer
+    pub fn is_null(&self) -> bool {
+        self.offset == 0
+    }
+
+    /// Resolve this offset from the `RelPtr` it was projected from, null
+    /// offsets resolve to null pointers
+    pub fn resolve_from<U>(&self, at: *const RelPtr<U>) -> *const T {
+        if self.is_null() {
+            core::ptr::null()
+        } else {
+            at.cast::<u8>().wrapping_offset(self.offset).cast()
+        }
+    }
+
+    /// A relative pointer to the field, which must be stored in place of the
+    /// `RelPtr` this was projected from
+    pub fn into_rel_ptr(self) -> RelPtr<T> {
+        RelPtr::from_offset(self.offset)
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{project::is_overlapping, Field};
+
+use core::{
+    cell::RefCell,
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut, Range},
+};
+use std::vec::Vec;
+
+/// Hands out unique references to the fields of a `&mut Parent` one at a
+/// time, as they are needed
+///
+/// Every borrow is checked against the fields that are still borrowed, and
+/// fails if they overlap. A field is given back when its [`SplitMut`] is
+/// dropped.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{Field, SplitBorrow};
+///
+/// #[derive(Field)]
+/// struct Window {
+///     title:  String,
+///     width:  u32,
+///     height: u32,
+/// }
+///
+/// fn main() {
+///     let fields = Window::fields();
+///     let mut window = Window {
+///         title:  String::new(),
+///         width:  10,
+///         height: 20,
+///     };
+///
+///     let split = SplitBorrow::new(&mut window);
+///     let mut width = split.borrow_mut(fields.width);
+///
+///     {
+///         let mut height = split.borrow_mut(fields.height);
+///         std::mem::swap(&mut *width, &mut *height);
+///     }
+///
+///     // `width` is still borrowed, but `height` was given back
+///     assert!(split.try_borrow_mut(fields.width).is_err());
+///     *split.borrow_mut(fields.height) += 1;
+///
+///     drop(width);
+///     split.borrow_mut(fields.title).push_str("resized");
+///
+///     assert_eq!((window.width, window.height), (20, 11));
+///     assert_eq!(window.title, "resized");
+/// }
+/// # }
+/// ```
+pub struct SplitBorrow<'a, Parent> {
+    ptr:      *mut Parent,
+    borrowed: RefCell<Vec<Range<usize>>>,
+    mark:     PhantomData<&'a mut Parent>,
+}
+
+/// A unique reference to a field borrowed from a [`SplitBorrow`]
+pub struct SplitMut<'s, T> {
+    value:    &'s mut T,
+    range:    Range<usize>,
+    borrowed: &'s RefCell<Vec<Range<usize>>>,
+}
+
+/// The error returned when a field overlaps a field that is still borrowed
+/// from a [`SplitBorrow`], or locked in a [`FieldLock`](crate::FieldLock)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BorrowError {
+    /// The bytes of the field that was requested
+    pub requested: Range<usize>,
+    /// The bytes of the borrowed field that it overlaps
+    pub borrowed:  Range<usize>,
+}
+
+impl fmt::Display for BorrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "field at {:?} overlaps the borrowed field at {:?}",
+            self.requested, self.borrowed
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BorrowError {
+}
+
+impl<'a, Parent> SplitBorrow<'a, Parent> {
+    /// Split borrow `parent`, no fields are borrowed yet
+    pub fn new(parent: &'a mut Parent) -> Self {
+        Self {
+            ptr:      parent,
+            borrowed: RefCell::new(Vec::new()),
+            mark:     PhantomData,
+        }
+    }
+
+    /// The bytes of every field that is currently borrowed
+    pub fn borrowed(&self) -> Vec<Range<usize>> {
+        self.borrowed.borrow().clone()
+    }
+
+    /// Borrow the field, if it doesn't overlap any borrowed fields
+    pub fn try_borrow_mut<F>(
+        &self,
+        field: F,
+    ) -> Result<SplitMut<'_, F::Type>, BorrowError>
+    where
+        F: Field<Parent = Parent>,
+    {
+        let range = field.range();
+        let mut borrowed = self.borrowed.borrow_mut();
+
+        if let Some(other) = borrowed
+            .iter()
+            .find(|other| is_overlapping(range.clone(), (*other).clone()))
+        {
+            return Err(BorrowError {
+                requested: range,
+                borrowed:  other.clone(),
+            })
+        }
+
+        // zero sized fields can't alias anything, so they aren't tracked
+        if !range.is_empty() {
+            borrowed.push(range.clone());
+        }
+
+        Ok(SplitMut {
+            // Safety: `ptr` came from a `&mut Parent` which lives for `'a`,
+            // and the field doesn't overlap any other borrowed field
+            value: unsafe { &mut *field.project_raw_mut(self.ptr) },
+            range,
+            borrowed: &self.borrowed,
+        })
+    }
+
+    /// Borrow the field
+    ///
+    /// # Panics
+    ///
+    /// If the field overlaps any borrowed fields
+    #[track_caller]
+    pub fn borrow_mut<F>(&self, field: F) -> SplitMut<'_, F::Type>
+    where
+        F: Field<Parent = Parent>,
+    {
+        match self.try_borrow_mut(field) {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl<T> SplitMut<'_, T> {
+    /// The bytes of the parent that this field covers
+    pub fn range(this: &Self) -> Range<usize> {
+        this.range.clone()
+    }
+}
+
+impl<T> Deref for SplitMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> DerefMut for SplitMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+impl<T> Drop for SplitMut<'_, T> {
+    fn drop(&mut self) {
+        let mut borrowed = self.borrowed.borrow_mut();
+
+        // ranges that are still borrowed never overlap, so there is at most
+        // one that matches
+        if let Some(index) = borrowed.iter().position(|r| *r == self.range) {
+            borrowed.swap_remove(index);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SplitMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
@@ -1,4 +1,13 @@
-use crate::Field;
+pub use crate::{
+    bits::RawBits,
+    bytes::{CloneField, CopyField, FieldsLen, ReadBytes, WriteBytes},
+};
+
+use crate::{Bits, Field, Shared};
+
+use core::{marker::PhantomData, ops::Range};
+#[cfg(feature = "alloc")]
+use std::vec::Vec;
 
 use typsy::{
     hlist::{Cons, Nil},
@@ -41,6 +50,35 @@ typsy::call! {
     fn[F: Field](&self: ProjectRawMut<F::Parent>, field: F) -> *mut F::Type {
         unsafe { field.project_raw_mut(self.0) }
     }
+
+    fn[F: Field](
+        &self: ProjectRaw<F::Parent>,
+        field: Shared<F>
+    ) -> *const F::Type {
+        unsafe { field.0.project_raw(self.0) }
+    }
+
+    // shared fields are never written to, even from a `*mut Parent`
+    fn[F: Field](
+        &self: ProjectRawMut<F::Parent>,
+        field: Shared<F>
+    ) -> *const F::Type {
+        unsafe { field.0.project_raw(self.0) }
+    }
+
+    fn[F: Field](
+        &self: ProjectRaw<F::Parent>,
+        bits: Bits<F>
+    ) -> RawBits<*const F::Type> {
+        unsafe { bits.project_raw(self.0) }
+    }
+
+    fn[F: Field](
+        &self: ProjectRawMut<F::Parent>,
+        bits: Bits<F>
+    ) -> RawBits<*mut F::Type> {
+        unsafe { bits.project_raw_mut(self.0) }
+    }
 }
 
 /// Represents a list of fields
@@ -49,6 +87,18 @@ pub trait FieldList<Parent>:
 {
 }
 
+/// Collects the ranges of every field in a list
+#[cfg(feature = "alloc")]
+pub struct Ranges<'a>(pub(crate) &'a mut Vec<Range<usize>>);
+
+#[cfg(feature = "alloc")]
+typsy::call! {
+    fn['a, F: Field](&mut self: Ranges<'a>, field: F) -> bool {
+        self.0.push(field.range());
+        false
+    }
+}
+
 impl<Parent> FieldList<Parent> for Nil {
 }
 
@@ -56,3 +106,145 @@ impl<F: Field, R> FieldList<F::Parent> for Cons<F, R> where
     Self: Map<ProjectRaw<F::Parent>> + Map<ProjectRawMut<F::Parent>>
 {
 }
+
+impl<F: Field, R> FieldList<F::Parent> for Cons<Shared<F>, R> where
+    Self: Map<ProjectRaw<F::Parent>> + Map<ProjectRawMut<F::Parent>>
+{
+}
+
+impl<F: Field, R> FieldList<F::Parent> for Cons<Bits<F>, R> where
+    Self: Map<ProjectRaw<F::Parent>> + Map<ProjectRawMut<F::Parent>>
+{
+}
+
+/// An entry of a field list, which is projected to either a shared or a
+/// unique reference
+pub trait Access {
+    /// If the entry is projected to a unique reference
+    const UNIQUE: bool;
+
+    /// The bytes of the parent that the entry covers
+    fn range(&self) -> Range<usize>;
+
+    /// The bits of the integer field that the entry covers, or `None` if it
+    /// covers all of the bytes in `range`
+    fn bit_range(&self) -> Option<Range<u32>> {
+        None
+    }
+}
+
+impl<F: Field> Access for F {
+    const UNIQUE: bool = true;
+
+    fn range(&self) -> Range<usize> {
+        Field::range(self)
+    }
+}
+
+impl<F: Field> Access for Shared<F> {
+    const UNIQUE: bool = false;
+
+    fn range(&self) -> Range<usize> {
+        self.0.range()
+    }
+}
+
+/// Compares the entries of two lists of the same type by the bytes they cover
+pub trait SameRanges {
+    /// Checks if each entry covers the same bytes as the entry at the same
+    /// index of `other`
+    fn same_ranges(&self, other: &Self) -> bool;
+}
+
+impl SameRanges for Nil {
+    fn same_ranges(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<T: Access, R: SameRanges> SameRanges for Cons<T, R> {
+    fn same_ranges(&self, other: &Self) -> bool {
+        self.value.range() == other.value.range()
+            && self.value.bit_range() == other.value.bit_range()
+            && self.rest.same_ranges(&other.rest)
+    }
+}
+
+/// The index of the first element of a list, used to find an element by type
+pub struct Here;
+
+/// The index of an element in the rest of a list
+pub struct There<I>(PhantomData<I>);
+
+/// Removes the element of type `T` at the index `I` from a list
+///
+/// `I` is always inferred, it is only needed to keep the impls from
+/// overlapping
+pub trait Pluck<T, I> {
+    /// The list without the element
+    type Remainder;
+
+    /// Split the element from the rest of the list
+    fn pluck(self) -> (T, Self::Remainder);
+}
+
+impl<T, R> Pluck<T, Here> for Cons<T, R> {
+    type Remainder = R;
+
+    fn pluck(self) -> (T, R) {
+        (self.value, self.rest)
+    }
+}
+
+impl<T, U, R: Pluck<T, I>, I> Pluck<T, There<I>> for Cons<U, R> {
+    type Remainder = Cons<U, R::Remainder>;
+
+    fn pluck(self) -> (T, Self::Remainder) {
+        let (value, rest) = self.rest.pluck();
+
+        (value, Cons {
+            value: self.value,
+            rest,
+        })
+    }
+}
+
+/// Removes all elements of the list `T` from a list, in any order
+///
+/// `I` is always inferred, it is a list of the indices for each element
+pub trait Sculpt<T, I> {
+    /// The list without the elements
+    type Remainder;
+
+    /// Split the elements from the rest of the list
+    fn sculpt(self) -> (T, Self::Remainder);
+}
+
+impl<L> Sculpt<Nil, Nil> for L {
+    type Remainder = L;
+
+    fn sculpt(self) -> (Nil, L) {
+        (Nil, self)
+    }
+}
+
+impl<L, T, R, I, J> Sculpt<Cons<T, R>, Cons<I, J>> for L
+where
+    L: Pluck<T, I>,
+    L::Remainder: Sculpt<R, J>,
+{
+    type Remainder = <L::Remainder as Sculpt<R, J>>::Remainder;
+
+    fn sculpt(self) -> (Cons<T, R>, Self::Remainder) {
+        let (value, rest) = self.pluck();
+        let (rest, remainder) = rest.sculpt();
+
+        (
+            Cons {
+                value,
+                rest,
+            },
+            remainder,
+        )
+    }
+}
@@ -0,0 +1,138 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::{Disjoint, Field, ProjectAll};
+use typsy::hlist;
+
+#[derive(Default, Field)]
+struct Packet {
+    header: u16,
+    len:    u8,
+}
+
+#[test]
+fn get_set() {
+    let mut packet = Packet {
+        header: 0xabcd,
+        len:    0,
+    };
+
+    let opcode = Packet::fields().header.bits(4..8);
+
+    assert_eq!(opcode.get(&packet), 0xc);
+    assert_eq!(opcode.mask(), 0x00f0);
+
+    opcode.set(&mut packet, 0x3);
+    assert_eq!(packet.header, 0xab3d);
+
+    // bits that don't fit are ignored
+    opcode.set(&mut packet, 0xf5);
+    assert_eq!(packet.header, 0xab5d);
+}
+
+#[test]
+fn proxies() {
+    let mut packet = Packet::default();
+
+    let flags = Packet::fields().header.bits(0..4);
+    let len = Packet::fields().len.bits(0..8);
+
+    let mut proxy = flags.project_mut(&mut packet);
+    proxy.set(0b1010);
+    assert_eq!(proxy.get(), 0b1010);
+
+    len.set(&mut packet, 7);
+    assert_eq!(flags.project(&packet).get(), 0b1010);
+    assert_eq!(len.project(&packet).get(), 7);
+
+    unsafe {
+        let mut proxy = flags.project_volatile(&mut packet);
+        proxy.set(0b0101);
+        assert_eq!(proxy.get(), 0b0101);
+    }
+
+    assert_eq!(packet.header, 0b0101);
+    assert_eq!(packet.len, 7);
+}
+
+#[test]
+fn overlap() {
+    let header = Packet::fields().header;
+    let len = Packet::fields().len;
+
+    assert!(header.bits(0..4).is_overlapping(&header.bits(3..8)));
+    assert!(!header.bits(0..4).is_overlapping(&header.bits(4..8)));
+    assert!(!header.bits(0..4).is_overlapping(&len.bits(0..4)));
+}
+
+#[test]
+#[should_panic]
+fn out_of_range() {
+    Packet::fields().len.bits(4..9);
+}
+
+#[derive(Field)]
+struct Reading {
+    value: i16,
+}
+
+#[test]
+fn signed() {
+    let mut reading = Reading {
+        value: -1,
+    };
+    let high = Reading::fields().value.bits(12..16);
+
+    // the bits aren't sign extended
+    assert_eq!(high.get(&reading), 0xf);
+
+    high.set(&mut reading, 0x7);
+    assert_eq!(reading.value, 0x7fff);
+}
+
+#[test]
+fn lists() {
+    let fields = Packet::fields();
+    let mut packet = Packet::default();
+
+    let list = Disjoint::new(hlist!(
+        fields.header.bits(0..4),
+        fields.header.bits(4..16),
+        fields.len,
+    ));
+
+    let typsy::hlist_pat!(mut low, mut high, len) =
+        (&mut packet).project_all(list);
+    low.set(0x3);
+    high.set(0x12);
+    *len = 1;
+    assert_eq!(low.get(), 0x3);
+
+    let header = fields.header;
+    let typsy::hlist_pat!(low, high) =
+        (&packet).project_all(hlist!(header.bits(0..4), header.bits(0..8)));
+    assert_eq!((low.get(), high.get()), (0x3, 0x23));
+
+    assert_eq!((packet.header, packet.len), (0x123, 1));
+
+    assert!(Disjoint::try_new(hlist!(
+        fields.header.bits(0..4),
+        fields.header.bits(3..8),
+    ))
+    .is_none());
+    assert!(Disjoint::try_new(hlist!(
+        fields.header.bits(0..4),
+        fields.header,
+    ))
+    .is_none());
+}
+
+#[test]
+#[should_panic(expected = "Found overlapping fields")]
+fn overlapping_list() {
+    let header = Packet::fields().header;
+    let mut packet = Packet::default();
+
+    let _ = (&mut packet)
+        .project_all(hlist!(header.bits(0..8), header.bits(7..9)));
+}
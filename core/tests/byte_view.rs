@@ -0,0 +1,69 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::*;
+use typsy::convert::Convert;
+
+#[derive(Clone, Copy, Field)]
+#[field(pod)]
+#[repr(C)]
+struct Header {
+    tag: u16,
+    len: u16,
+    sub: Sub,
+}
+
+#[derive(Clone, Copy, Field)]
+#[field(pod)]
+#[repr(C)]
+struct Sub([u8; 3], u8);
+
+#[repr(align(4))]
+struct Buffer([u8; 9]);
+
+fn buffer() -> Buffer {
+    let mut buffer = Buffer([0; 9]);
+    buffer.0[..2].copy_from_slice(&0x1234_u16.to_ne_bytes());
+    buffer.0[2..4].copy_from_slice(&8_u16.to_ne_bytes());
+    buffer.0[4..8].copy_from_slice(&[1, 2, 3, 4]);
+    buffer
+}
+
+#[test]
+fn project() {
+    let buffer = buffer();
+    let view = ByteView::<Header>::new(&buffer.0).unwrap();
+
+    let header = Header::fields();
+    let sub = Sub::fields();
+
+    assert_eq!(*view.project_to(header.tag), 0x1234);
+    assert_eq!(*view.project_to(header.sub.chain(sub.1)), 4);
+    assert_eq!(view.get().len, 8);
+
+    let typsy::hlist_pat!(len, arr) =
+        view.project_all((header.len, header.sub.chain(sub.0)).into_hlist());
+
+    assert_eq!(*len, 8);
+    assert_eq!(*arr, [1, 2, 3]);
+}
+
+#[test]
+fn errors() {
+    let buffer = buffer();
+
+    assert_eq!(
+        ByteView::<Header>::new(&buffer.0[..7]).err(),
+        Some(ByteViewError::TooShort {
+            needed: 8,
+            len:    7,
+        })
+    );
+
+    assert_eq!(
+        ByteView::<Header>::new(&buffer.0[1..]).err(),
+        Some(ByteViewError::Misaligned {
+            align: 2,
+        })
+    );
+}
@@ -0,0 +1,117 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::*;
+use typsy::convert::Convert;
+
+#[derive(Default, Clone, Copy, Field)]
+#[field(pod)]
+#[repr(C)]
+struct Message {
+    kind:     u8,
+    // 3 bytes of padding
+    len:      u32,
+    checksum: [u8; 2],
+}
+
+#[derive(Clone, Copy, Field)]
+#[field(pod, as_bytes)]
+#[repr(C)]
+struct Dense(u16, [u8; 2]);
+
+#[test]
+fn field_bytes() {
+    let mut message = Message::default();
+    let fields = Message::fields();
+
+    fields.len.bytes_mut(&mut message).copy_from_slice(&[1, 2, 3, 4]);
+
+    assert_eq!(message.len, u32::from_ne_bytes([1, 2, 3, 4]));
+    assert_eq!(fields.len.bytes(&message), &[1, 2, 3, 4]);
+    assert_eq!(fields.checksum.bytes(&message), &[0, 0]);
+
+    let dense = Dense(0x0102, [3, 4]);
+    let mut bytes = 0x0102_u16.to_ne_bytes().to_vec();
+    bytes.extend_from_slice(&[3, 4]);
+    assert_eq!(Identity::<Dense>::NEW.bytes(&dense), &bytes[..]);
+}
+
+#[test]
+fn copy() {
+    let src = Message {
+        kind:     1,
+        len:      2,
+        checksum: [3, 4],
+    };
+    let mut dst = Message::default();
+    let fields = Message::fields();
+
+    fields.kind.copy_from(&mut dst, &src);
+    assert_eq!(dst.kind, 1);
+
+    copy_fields(&mut dst, &src, (fields.len, fields.checksum).into_hlist());
+    assert_eq!(dst.len, 2);
+    assert_eq!(dst.checksum, [3, 4]);
+}
+
+// the bounds of the list functions can be written outside of the crate
+fn copy_and_len<L>(dst: &mut Message, src: &Message, fields: L) -> usize
+where
+    L: Copy
+        + for<'a> typsy::cmp::Any<'a, type_list::CopyField<Message>>
+        + for<'a> typsy::cmp::Any<'a, type_list::FieldsLen>,
+{
+    copy_fields(dst, src, fields);
+    fields_len(fields)
+}
+
+#[test]
+fn named_bounds() {
+    let src = Message {
+        kind:     1,
+        len:      2,
+        checksum: [3, 4],
+    };
+    let mut dst = Message::default();
+    let fields = Message::fields();
+
+    let list = (fields.kind, fields.checksum).into_hlist();
+    assert_eq!(copy_and_len(&mut dst, &src, list), 3);
+    assert_eq!((dst.kind, dst.len, dst.checksum), (1, 0, [3, 4]));
+}
+
+#[test]
+fn write_read() {
+    let src = Message {
+        kind:     1,
+        len:      0x0203_0405,
+        checksum: [6, 7],
+    };
+    let fields = Message::fields();
+    let list = (fields.kind, fields.len, fields.checksum).into_hlist();
+
+    assert_eq!(fields_len(list), 7);
+
+    let mut buffer = [0; 8];
+    assert_eq!(write_fields(&src, list, &mut buffer), 7);
+
+    let mut expected = vec![1];
+    expected.extend_from_slice(&0x0203_0405_u32.to_ne_bytes());
+    expected.extend_from_slice(&[6, 7, 0]);
+    assert_eq!(&buffer[..], &expected[..]);
+
+    let mut dst = Message::default();
+    assert_eq!(read_fields(&mut dst, list, &buffer), 7);
+    assert_eq!(dst.kind, 1);
+    assert_eq!(dst.len, 0x0203_0405);
+    assert_eq!(dst.checksum, [6, 7]);
+}
+
+#[test]
+#[should_panic]
+fn short_buffer() {
+    let fields = Message::fields();
+    let list = (fields.kind, fields.len).into_hlist();
+
+    write_fields(&Message::default(), list, &mut [0; 4]);
+}
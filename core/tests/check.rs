@@ -0,0 +1,131 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::{check_field, Field, FieldError};
+
+#[derive(Field)]
+#[repr(C)]
+struct Foo {
+    a: u16,
+    b: [u8; 2],
+    c: u32,
+}
+
+struct Project<F>(F, F);
+
+unsafe impl<T> Field for Project<unsafe fn(*const Foo) -> *const T> {
+    type Parent = Foo;
+    type Type = T;
+
+    unsafe fn project_raw(&self, ptr: *const Foo) -> *const T {
+        (self.0)(ptr)
+    }
+
+    unsafe fn project_raw_mut(&self, ptr: *mut Foo) -> *mut T {
+        (self.1)(ptr) as *mut T
+    }
+}
+
+fn project<T>(
+    project: unsafe fn(*const Foo) -> *const T,
+    project_mut: unsafe fn(*const Foo) -> *const T,
+) -> Project<unsafe fn(*const Foo) -> *const T> {
+    Project(project, project_mut)
+}
+
+#[test]
+fn derived() {
+    let fields = Foo::fields();
+
+    assert_eq!(check_field(&fields.a), Ok(()));
+    assert_eq!(check_field(&fields.b), Ok(()));
+    assert_eq!(check_field(&fields.c), Ok(()));
+    assert_eq!(check_field(&fields.c.dynamic()), Ok(()));
+}
+
+#[test]
+fn broken() {
+    unsafe fn c(ptr: *const Foo) -> *const u32 {
+        &raw const (*ptr).c
+    }
+
+    unsafe fn a(ptr: *const Foo) -> *const u32 {
+        ptr.cast()
+    }
+
+    unsafe fn c_wide(ptr: *const Foo) -> *const u64 {
+        (&raw const (*ptr).c).cast()
+    }
+
+    unsafe fn b_unaligned(ptr: *const Foo) -> *const u32 {
+        (&raw const (*ptr).b).cast()
+    }
+
+    // `Foo` is 8 bytes, so this picks a different field in each parent that
+    // `check_field` projects from
+    unsafe fn by_address(ptr: *const Foo) -> *const u32 {
+        if ptr as usize & 8 == 0 {
+            &raw const (*ptr).c
+        } else {
+            ptr.cast()
+        }
+    }
+
+    assert_eq!(check_field(&project(c, c)), Ok(()));
+    assert_eq!(
+        check_field(&project(c, a)),
+        Err(FieldError::ProjectionMismatch {
+            offset:     4,
+            offset_mut: 0,
+        })
+    );
+    assert_eq!(
+        check_field(&project(c_wide, c_wide)),
+        Err(FieldError::OutOfBounds {
+            offset:      4,
+            size:        8,
+            parent_size: 8,
+        })
+    );
+    assert_eq!(
+        check_field(&project(b_unaligned, b_unaligned)),
+        Err(FieldError::Misaligned {
+            offset: 2,
+            align:  4,
+        })
+    );
+    assert!(check_field(&project(by_address, by_address)).is_err());
+}
+
+#[derive(Field)]
+#[repr(C, packed)]
+struct Packed {
+    a: u8,
+    b: u32,
+    c: [u8; 3],
+    d: u32,
+}
+
+#[test]
+fn packed() {
+    let fields = Packed::fields();
+
+    // `Packed` is only aligned to 1 byte, so the fields are checked by their
+    // offsets, which don't depend on where the parent is
+    assert_eq!(check_field(&fields.a), Ok(()));
+    assert_eq!(
+        check_field(&fields.b),
+        Err(FieldError::Misaligned {
+            offset: 1,
+            align:  4,
+        })
+    );
+    assert_eq!(check_field(&fields.d), Ok(()));
+}
+
+#[test]
+#[cfg(feature = "checked")]
+#[should_panic]
+fn checked_dynamic() {
+    unsafe { gfp_core::Dynamic::<Foo, u32>::from_offset(6) };
+}
@@ -0,0 +1,102 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{CopyNamed, Field};
+
+#[derive(Field)]
+struct Row {
+    id:       u32,
+    email:    String,
+    verified: bool,
+    created:  u64,
+}
+
+#[derive(Debug, Default, PartialEq, Field)]
+#[field(copy_from(Row, Dto))]
+struct User {
+    id:       u64,
+    email:    String,
+    verified: bool,
+    #[field(skip_copy)]
+    sessions: Vec<u32>,
+}
+
+#[derive(Field)]
+struct Dto {
+    id:       u8,
+    email:    &'static str,
+    verified: bool,
+    sessions: Vec<u32>,
+}
+
+#[derive(Debug, Default, PartialEq, Field)]
+#[field(copy_from(Pair))]
+struct Wide(u64, String);
+
+#[derive(Field)]
+struct Pair(u32, &'static str);
+
+#[derive(Debug, Default, PartialEq, Field)]
+#[field(copy_from(Row))]
+struct Generic<T: Default> {
+    id:    u64,
+    #[field(skip_copy)]
+    extra: T,
+}
+
+#[test]
+fn named() {
+    let row = Row {
+        id:       7,
+        email:    String::from("a@b.c"),
+        verified: true,
+        created:  100,
+    };
+
+    let mut user = User::from_named(&row);
+    assert_eq!(user, User {
+        id:       7,
+        email:    String::from("a@b.c"),
+        verified: true,
+        sessions: Vec::new(),
+    });
+
+    user.sessions.push(1);
+    user.copy_named(&Dto {
+        id:       8,
+        email:    "d@e.f",
+        verified: false,
+        sessions: vec![2, 3],
+    });
+
+    assert_eq!(user, User {
+        id:       8,
+        email:    String::from("d@e.f"),
+        verified: false,
+        sessions: vec![1],
+    });
+}
+
+#[test]
+fn tuple() {
+    let wide = Wide::from_named(&Pair(1, "one"));
+    assert_eq!(wide, Wide(1, String::from("one")));
+}
+
+#[test]
+fn generic() {
+    let row = Row {
+        id:       3,
+        email:    String::new(),
+        verified: false,
+        created:  0,
+    };
+
+    let mut value = Generic {
+        id:    0,
+        extra: String::from("kept"),
+    };
+    value.copy_named(&row);
+
+    assert_eq!(value.id, 3);
+    assert_eq!(value.extra, "kept");
+}
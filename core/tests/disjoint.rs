@@ -0,0 +1,96 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::{Disjoint, Field, PinToPin, PinToPtr, ProjectAll};
+use typsy::convert::Convert;
+
+use std::{pin::Pin, rc::Rc, sync::Arc};
+
+#[derive(Default, Field)]
+struct Foo {
+    x: u8,
+    y: Bar,
+    z: u32,
+}
+
+#[derive(Default, Field)]
+struct Bar {
+    a: u16,
+    b: u32,
+}
+
+#[test]
+fn mutable() {
+    let foo = Foo::fields();
+    let bar = Bar::fields();
+
+    let list = Disjoint::new((foo.x, foo.y.chain(bar.b), foo.z).into_hlist());
+    let mut value = Foo::default();
+
+    for _ in 0..10 {
+        let typsy::hlist_pat!(x, y_b, z) = (&mut value).project_all(list);
+        *x += 1;
+        *y_b += 2;
+        *z += *y_b;
+    }
+
+    assert_eq!(value.x, 10);
+    assert_eq!(value.y.b, 20);
+    assert_eq!(value.z, 110);
+}
+
+#[test]
+fn shared() {
+    let foo = Foo::fields();
+    let list = Disjoint::new((foo.x, foo.z).into_hlist());
+
+    let mut value = Foo::default();
+    value.x = 1;
+    value.z = 2;
+
+    let typsy::hlist_pat!(x, z) = (&value).project_all(list);
+    assert_eq!((*x, *z), (1, 2));
+
+    let rc = Rc::new(Foo::default()).project_all(list);
+    let typsy::hlist_pat!(x, z) = rc.get();
+    assert_eq!((*x, *z), (0, 0));
+
+    let arc = Arc::new(Foo::default()).project_all(list);
+    let typsy::hlist_pat!(x, z) = arc.get();
+    assert_eq!((*x, *z), (0, 0));
+}
+
+#[test]
+fn pin() {
+    let foo = Foo::fields();
+    let list = Disjoint::new(
+        (unsafe { PinToPin::new_unchecked(foo.x) }, PinToPtr::new(foo.z))
+            .into_hlist(),
+    );
+
+    let mut value = Foo::default();
+    let typsy::hlist_pat!(mut x, z) = Pin::new(&mut value).project_all(list);
+    *x.as_mut() = 3;
+    *z = 4;
+
+    assert_eq!((value.x, value.z), (3, 4));
+}
+
+#[test]
+fn overlap() {
+    let foo = Foo::fields();
+    let bar = Bar::fields();
+
+    let list = (foo.y, foo.y.chain(bar.a)).into_hlist();
+    assert!(Disjoint::try_new(list).is_none());
+
+    let list = (foo.x, foo.y.chain(bar.a)).into_hlist();
+    assert!(Disjoint::try_new(list).is_some());
+}
+
+#[test]
+#[should_panic]
+fn overlap_panics() {
+    let foo = Foo::fields();
+    Disjoint::new((foo.x, foo.x).into_hlist());
+}
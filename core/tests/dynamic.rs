@@ -0,0 +1,67 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::{Dynamic, Field, ProjectTo};
+
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Default, Field)]
+struct Foo {
+    x: u8,
+    y: Bar,
+}
+
+#[derive(Default, Field)]
+struct Bar {
+    a: u16,
+    c: Quaz,
+}
+
+#[derive(Default, Field)]
+struct Quaz {
+    q: u32,
+    r: u64,
+}
+
+#[test]
+fn compose() {
+    let mut foo = Foo::default();
+    foo.y.c.r = 7;
+
+    let y = Foo::fields().y.dynamic();
+    let c = Bar::fields().c.dynamic();
+    let r = Quaz::fields().r.dynamic();
+
+    let y_c_r = y.compose(c).compose(r);
+    assert_eq!(y_c_r, y + (c + r));
+    assert_eq!(y_c_r, Dynamic::identity() + y_c_r);
+    assert_eq!(*foo.project_to(y_c_r), 7);
+
+    let chain = Foo::fields()
+        .y
+        .chain(Bar::fields().c)
+        .chain(Quaz::fields().r);
+    assert_eq!(Dynamic::from(chain), y_c_r);
+    assert_eq!(y_c_r.offset(), chain.field_offset());
+}
+
+#[test]
+fn keys() {
+    let x = Foo::fields().x.dynamic();
+    let c: Dynamic<Foo, Quaz> = Foo::fields().y.chain(Bar::fields().c).into();
+    let q = c.compose(Dynamic::<Quaz, u32>::from_name("q").unwrap());
+
+    let set = vec![q, q, Dynamic::from_path("y.c.q").unwrap()]
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+    assert_eq!(set.len(), 1);
+
+    let mut names = HashMap::new();
+    names.insert(x, "x");
+    names.insert(Foo::fields().x.dynamic(), "still x");
+    assert_eq!(names.len(), 1);
+    assert_eq!(names[&x], "still x");
+
+    let debug = format!("Dynamic {{ offset: {} }}", x.offset());
+    assert_eq!(format!("{:?}", x), debug);
+}
@@ -0,0 +1,84 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::{Dynamic, ErasedField, Field, PathError, ProjectTo};
+
+use std::any::TypeId;
+
+#[derive(Field)]
+struct Foo {
+    x: u8,
+    y: Bar,
+}
+
+#[derive(Field)]
+struct Bar {
+    name: String,
+    len:  u32,
+}
+
+fn foo() -> Foo {
+    Foo {
+        x: 1,
+        y: Bar {
+            name: String::from("bar"),
+            len:  3,
+        },
+    }
+}
+
+#[test]
+fn downcast() {
+    let foo = foo();
+    let fields = Foo::fields();
+
+    let x = fields.x.dynamic().erase();
+    let len: ErasedField<Foo> =
+        fields.y.chain(Bar::fields().len).dynamic().into();
+
+    assert_eq!(x.type_id(), TypeId::of::<u8>());
+    assert_eq!(x.type_name(), "u8");
+    assert_eq!(len.size(), 4);
+    assert_eq!(len.range(), fields.y.chain(Bar::fields().len).range());
+
+    assert!(x.downcast::<u32>().is_none());
+    assert_eq!(*foo.project_to(x.downcast::<u8>().unwrap()), 1);
+    assert_eq!(*foo.project_to(len.downcast::<u32>().unwrap()), 3);
+}
+
+#[test]
+fn project() {
+    let mut foo = foo();
+
+    let fields = [
+        ErasedField::<Foo>::from_name("x").unwrap(),
+        ErasedField::<Foo>::from_path("y.name").unwrap(),
+        ErasedField::<Foo>::from_path("y.len").unwrap(),
+    ];
+
+    assert_eq!(fields[0].project(&foo).downcast_ref::<u8>(), Some(&1));
+    assert_eq!(
+        fields[1].project(&foo).downcast_ref::<String>().unwrap(),
+        "bar"
+    );
+
+    *fields[2].project_mut(&mut foo).downcast_mut::<u32>().unwrap() = 10;
+    assert_eq!(foo.y.len, 10);
+
+    assert_eq!(
+        ErasedField::<Foo>::from_path("y.size").err(),
+        Some(PathError::UnknownField("size"))
+    );
+}
+
+#[test]
+fn overlap() {
+    let y = ErasedField::<Foo>::from_name("y").unwrap();
+    let x = ErasedField::<Foo>::from_name("x").unwrap();
+    let len = Dynamic::<Foo, u32>::from_path("y.len").unwrap();
+
+    assert!(!x.is_overlapping(&y));
+    assert!(y.is_overlapping(&len.erase()));
+    assert!(y.is_overlapping_field(&len));
+    assert!(!x.is_overlapping_field(&Foo::fields().y));
+}
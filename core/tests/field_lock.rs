@@ -0,0 +1,107 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{BorrowError, Field, FieldLock};
+use typsy::hlist;
+
+use std::{sync::mpsc, thread, time::Duration};
+
+#[derive(Default, Field)]
+#[repr(C)]
+struct Counters {
+    a:     u32,
+    b:     u32,
+    inner: Inner,
+}
+
+#[derive(Debug, Default, Field)]
+#[repr(C)]
+struct Inner {
+    c: u16,
+    d: u16,
+}
+
+#[test]
+fn threads() {
+    let fields = Counters::fields();
+    let lock = FieldLock::new(Counters::default());
+
+    thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    *lock.lock(fields.a) += 1;
+
+                    let mut guard = lock.lock_all(hlist!(fields.a, fields.b));
+                    let typsy::hlist_pat!(a, b) = guard.get_mut();
+                    *b += *a % 2;
+                }
+            });
+        }
+    });
+
+    let counters = lock.into_inner();
+    assert_eq!(counters.a, 800);
+    assert!(counters.b <= 800);
+}
+
+#[test]
+fn try_lock() {
+    let fields = Counters::fields();
+    let inner = Inner::fields();
+    let lock = FieldLock::new(Counters::default());
+
+    let c = lock.lock(fields.inner.chain(inner.c));
+    let d = lock.try_lock(fields.inner.chain(inner.d)).unwrap();
+
+    assert_eq!(
+        lock.try_lock(fields.inner).unwrap_err(),
+        BorrowError {
+            requested: 8..12,
+            borrowed:  8..10,
+        }
+    );
+    assert!(lock.try_lock_all(hlist!(fields.a, fields.inner)).is_err());
+
+    drop((c, d));
+    let guard = lock.try_lock_all(hlist!(fields.a, fields.inner)).unwrap();
+    let typsy::hlist_pat!(a, inner) = guard.get();
+    assert_eq!((*a, inner.c, inner.d), (0, 0, 0));
+}
+
+#[test]
+fn blocks() {
+    let fields = Counters::fields();
+    let lock = FieldLock::new(Counters::default());
+    let (send, recv) = mpsc::channel();
+
+    let mut guard = lock.lock(fields.b);
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            // doesn't overlap, so this never blocks
+            *lock.lock(fields.a) = 1;
+            send.send(()).unwrap();
+
+            let mut b = lock.lock(fields.b);
+            assert_eq!(*b, 2);
+            *b = 3;
+        });
+
+        recv.recv().unwrap();
+        thread::sleep(Duration::from_millis(10));
+        *guard = 2;
+        drop(guard);
+    });
+
+    let counters = lock.into_inner();
+    assert_eq!((counters.a, counters.b), (1, 3));
+}
+
+#[test]
+#[should_panic(expected = "Found overlapping fields")]
+fn overlapping() {
+    let fields = Counters::fields();
+    let lock = FieldLock::new(Counters::default());
+
+    let _ = lock.lock_all(hlist!(fields.inner, fields.a, fields.inner));
+}
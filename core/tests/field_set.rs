@@ -0,0 +1,90 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::{Dynamic, ErasedField, Field, FieldSet, OverlapError};
+
+#[derive(Default, Field)]
+struct Row {
+    id:    u32,
+    name:  String,
+    price: u32,
+    pos:   Pos,
+}
+
+#[derive(Default, Field)]
+struct Pos {
+    x: u32,
+    y: u32,
+}
+
+fn column(path: &str) -> Dynamic<Row, u32> {
+    Dynamic::from_path(path).unwrap()
+}
+
+#[test]
+fn project() {
+    let mut row = Row::default();
+
+    let columns = vec![column("pos.y"), column("id"), column("pos.x")];
+    let set = FieldSet::new(columns).unwrap();
+    assert_eq!(set.len(), 3);
+
+    for (i, value) in set.project_mut(&mut row).into_iter().enumerate() {
+        *value = i as u32 + 1;
+    }
+
+    assert_eq!((row.id, row.pos.x, row.pos.y), (2, 3, 1));
+    assert_eq!(set.project(&row), [&1, &2, &3]);
+
+    let [y, id, x] = set.project_array_mut(&mut row).unwrap();
+    std::mem::swap(x, y);
+    *id = 0;
+    assert_eq!((row.id, row.pos.x, row.pos.y), (0, 1, 3));
+
+    assert!(set.project_array_mut::<2>(&mut row).is_none());
+}
+
+#[test]
+fn overlap() {
+    let id = column("id");
+    let pos = Row::fields().pos.dynamic();
+
+    assert_eq!(
+        FieldSet::new(vec![id, column("pos.x"), column("price"), id]).err(),
+        Some(OverlapError {
+            first:  0,
+            second: 3,
+        })
+    );
+
+    let erased = vec![
+        column("pos.y").erase(),
+        ErasedField::from_name("name").unwrap(),
+        pos.erase(),
+    ];
+    assert_eq!(
+        FieldSet::new_erased(erased).err(),
+        Some(OverlapError {
+            first:  0,
+            second: 2,
+        })
+    );
+}
+
+#[test]
+fn erased() {
+    let mut row = Row::default();
+
+    let set = FieldSet::new_erased(vec![
+        ErasedField::from_name("name").unwrap(),
+        ErasedField::from_path("pos.x").unwrap(),
+    ])
+    .unwrap();
+
+    let mut values = set.project_mut(&mut row);
+    values[0].downcast_mut::<String>().unwrap().push_str("row");
+    *values[1].downcast_mut::<u32>().unwrap() = 4;
+
+    assert_eq!(row.name, "row");
+    assert_eq!(row.pos.x, 4);
+}
@@ -0,0 +1,132 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{
+    field_name, Field, FieldAt, HasField, HasFields, HasUnionFields,
+    ProjectTo,
+};
+
+#[derive(Default, Field)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Default, Field)]
+struct Pair<T>(T, T);
+
+#[derive(Field)]
+union Word {
+    int:   u32,
+    bytes: [u8; 4],
+}
+
+fn fields<T: HasFields>() -> T::Fields {
+    T::fields()
+}
+
+fn set_both<T: Copy, P: HasFields<Fields = PairFields<T>>>(
+    parent: &mut Pair<T>,
+    value: T,
+) {
+    let fields = P::FIELDS;
+
+    *parent.project_to(fields.0) = value;
+    *parent.project_to(fields.1) = value;
+}
+
+#[test]
+fn structs() {
+    let fields = fields::<Point>();
+    let mut point = Point::default();
+
+    *(&mut point).project_to(fields.y) = 3;
+    assert_eq!(point.y, 3);
+
+    let mut pair = Pair::default();
+    set_both::<u8, Pair<u8>>(&mut pair, 7);
+    assert_eq!((pair.0, pair.1), (7, 7));
+}
+
+#[test]
+fn copy() {
+    let fields = Point::FIELDS;
+    let copy = fields;
+
+    assert_eq!(fields.x.range(), copy.x.range());
+}
+
+#[test]
+fn unions() {
+    let fields = unsafe { <Word as HasUnionFields>::fields() };
+
+    assert_eq!(fields.int.range(), 0..4);
+    assert_eq!(fields.bytes.range(), 0..4);
+}
+
+#[derive(Default, Field)]
+struct Account<T> {
+    owner:  T,
+    r#type: u8,
+}
+
+fn take_owner<T, P>(parent: &mut P) -> T
+where
+    T: Default,
+    P: HasField<field_name!(owner), Type = T>,
+{
+    std::mem::take(parent.project_to(P::FIELD))
+}
+
+#[test]
+fn by_name() {
+    let mut account = Account {
+        owner:  String::from("root"),
+        r#type: 1,
+    };
+
+    assert_eq!(take_owner(&mut account), "root");
+    assert_eq!(account.owner, "");
+
+    let field = <Account<String> as HasField<field_name!(r#type)>>::FIELD;
+    assert_eq!(*(&account).project_to(field), 1);
+
+    let field = <Pair<u8> as HasField<field_name!(1)>>::FIELD;
+    assert_eq!(field.range(), 1..2);
+}
+
+fn compare_first_two<T>(a: &T, b: &T) -> std::cmp::Ordering
+where
+    T: FieldAt<0> + FieldAt<1>,
+    <T as FieldAt<0>>::Type: Ord,
+    <T as FieldAt<1>>::Type: Ord,
+{
+    let first = <T as FieldAt<0>>::FIELD;
+    let second = <T as FieldAt<1>>::FIELD;
+
+    a.project_to(first)
+        .cmp(b.project_to(first))
+        .then_with(|| a.project_to(second).cmp(b.project_to(second)))
+}
+
+#[test]
+fn by_position() {
+    use std::cmp::Ordering;
+
+    let a = Point {
+        x: 1,
+        y: 2,
+    };
+    let b = Point {
+        x: 1,
+        y: 3,
+    };
+
+    assert_eq!(compare_first_two(&a, &b), Ordering::Less);
+    assert_eq!(compare_first_two(&Pair(2, 0), &Pair(1, 5)), Ordering::Greater);
+
+    assert_eq!(<Account<u8> as FieldAt<1>>::FIELD.range(), 1..2);
+
+    assert_eq!(Point::FIELD_COUNT, 2);
+    assert_eq!(<Account<u8>>::FIELD_COUNT, 2);
+    assert_eq!(<Word as HasUnionFields>::FIELD_COUNT, 2);
+}
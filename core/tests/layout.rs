@@ -0,0 +1,100 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::{Field, LayoutEntry, Reflect};
+
+use std::mem::{align_of, size_of};
+
+#[derive(Field)]
+#[repr(C)]
+struct Foo {
+    a: u8,
+    b: u64,
+    c: u16,
+}
+
+#[derive(Field)]
+#[repr(C)]
+union Bar {
+    a: u8,
+    b: [u16; 3],
+    c: u32,
+}
+
+// the layouts depend on the alignment of `u64` and `u32`, which is different
+// between targets
+fn foo_ranges() -> [std::ops::Range<usize>; 3] {
+    let align = align_of::<u64>();
+    [0..1, align..align + 8, align + 8..align + 10]
+}
+
+#[test]
+fn entries() {
+    let layout = Foo::layout();
+    let [a, b, c] = foo_ranges();
+    let size = size_of::<Foo>();
+
+    let entries = layout
+        .entries()
+        .map(|entry| match entry {
+            LayoutEntry::Field(field) => (Some(field.name()), field.range()),
+            LayoutEntry::Padding(range) => (None, range),
+        })
+        .collect::<Vec<_>>();
+
+    let expected = [
+        (Some("a"), a.clone()),
+        (None, a.end..b.start),
+        (Some("b"), b.clone()),
+        (Some("c"), c.clone()),
+        (None, c.end..size),
+    ];
+    let expected = expected
+        .iter()
+        .filter(|(_, range)| !range.is_empty())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    assert_eq!(entries, expected);
+    assert_eq!(layout.size(), size);
+    assert_eq!(layout.padding(), size - 11);
+}
+
+#[test]
+fn union() {
+    let layout = Bar::layout();
+    let size = size_of::<Bar>();
+
+    assert_eq!(layout.padding(), size - 6);
+    assert_eq!(
+        layout.lines().to_string(),
+        format!(
+            "type size={} align={}
+field name=a offset=0 size=1 align=1
+field name=b offset=0 size=6 align={}
+field name=c offset=0 size=4 align={}
+padding offset=6 size={}
+",
+            size,
+            align_of::<Bar>(),
+            align_of::<u16>(),
+            align_of::<u32>(),
+            size - 6,
+        )
+    );
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn display() {
+    let layout = Foo::layout().to_string();
+    let mut lines = layout.lines();
+
+    assert!(lines.next().unwrap().ends_with("Foo (size 24, align 8)"));
+    assert_eq!(lines.next(), Some("      0..1     a: u8"));
+    assert_eq!(lines.next(), Some("      1..8     <7 bytes of padding>"));
+    assert_eq!(lines.next(), Some("      8..16    b: u64"));
+    assert_eq!(lines.next(), Some("     16..18    c: u16"));
+    assert_eq!(lines.next(), Some("     18..24    <6 bytes of padding>"));
+    assert_eq!(lines.next(), None);
+}
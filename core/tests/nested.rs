@@ -0,0 +1,90 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::{field_path, Field, FieldPath, ProjectAll, ProjectTo};
+
+#[derive(Default, Field)]
+struct Foo {
+    x: u8,
+    y: Bar,
+}
+
+#[derive(Default, Field)]
+struct Bar(u16, Quaz<u32>);
+
+#[derive(Default, Field)]
+struct Quaz<T> {
+    q:     T,
+    inner: Inner<T>,
+}
+
+#[derive(Default, Field)]
+struct Inner<T> {
+    value: T,
+}
+
+const FOO_TO_Q: FieldPath!(Foo.y.1.q) = field_path!(Foo.y.1.q);
+
+#[test]
+fn deref() {
+    let nested = Foo::fields().y.1.q;
+    let chained = Foo::fields().y.chain(Bar::fields().1).chain(Quaz::fields().q);
+
+    assert_eq!(nested.range(), chained.range());
+    assert_eq!(FOO_TO_Q.range(), chained.range());
+
+    let mut foo = Foo::default();
+    *(&mut foo).project_to(nested) = 10;
+    assert_eq!(foo.y.1.q, 10);
+}
+
+#[test]
+fn path() {
+    let mut foo = Foo::default();
+
+    *(&mut foo).project_to(field_path!(Foo.y.0)) = 3;
+    *(&mut foo).project_to(field_path!(Foo.y.1.inner.value)) = 4;
+
+    assert_eq!(foo.y.0, 3);
+    assert_eq!(foo.y.1.inner.value, 4);
+}
+
+#[test]
+fn generic() {
+    let fields = Quaz::<u8>::fields();
+    let mut quaz = Quaz::<u8>::default();
+
+    let typsy::hlist_pat!(q, value) = (&mut quaz)
+        .project_all(typsy::hlist!(fields.q, fields.inner.value));
+    *q = 1;
+    *value = 2;
+
+    assert_eq!(quaz.q, 1);
+    assert_eq!(quaz.inner.value, 2);
+}
+
+// the fields structs of public types are used in public trait impls
+pub mod public {
+    use gfp_core::{Field, ProjectTo};
+
+    #[derive(Default, Field)]
+    pub struct Outer {
+        pub inner: Inner<u8>,
+    }
+
+    #[derive(Default, Field)]
+    pub struct Inner<T> {
+        pub value: T,
+    }
+
+    pub fn set(outer: &mut Outer, value: u8) {
+        *outer.project_to(Outer::fields().inner.value) = value;
+    }
+}
+
+#[test]
+fn public() {
+    let mut outer = public::Outer::default();
+    public::set(&mut outer, 5);
+    assert_eq!(outer.inner.value, 5);
+}
@@ -0,0 +1,155 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Disjoint, Dynamic, Field, Partial};
+use typsy::hlist;
+
+#[derive(Default, Field)]
+struct World {
+    tick:      u64,
+    positions: Vec<f32>,
+    scores:    Vec<u32>,
+    player:    Player,
+}
+
+#[derive(Default, Field)]
+struct Player {
+    name:   String,
+    health: u8,
+}
+
+#[test]
+fn get() {
+    let fields = World::fields();
+    let mut world = World::default();
+
+    let list = Disjoint::new(hlist!(fields.tick, fields.scores));
+    let mut partial = Partial::new(&mut world, list);
+
+    *partial.get_mut(fields.tick) += 1;
+    partial.get_mut(fields.scores).push(3);
+    assert_eq!(*partial.get(fields.tick), 1);
+
+    let mut again = partial.reborrow();
+    *again.get_mut(fields.tick) += 1;
+
+    let typsy::hlist_pat!(tick, scores) = partial.project_all();
+    *tick += 1;
+    scores.push(4);
+
+    assert_eq!(world.tick, 3);
+    assert_eq!(world.scores, [3, 4]);
+}
+
+#[test]
+fn split() {
+    let fields = World::fields();
+    let mut world = World::default();
+
+    let list = Disjoint::new(hlist!(
+        fields.tick,
+        fields.positions,
+        fields.scores,
+        fields.player.health,
+    ));
+    let partial = Partial::new(&mut world, list);
+
+    // in a different order than the list
+    let (mut left, right) =
+        partial.split(hlist!(fields.scores, fields.player.health));
+    let (mut tick, mut positions) = right.split(hlist!(fields.tick));
+
+    *tick.get_mut(fields.tick) = 10;
+    positions.get_mut(fields.positions).push(1.0);
+    left.get_mut(fields.scores).push(2);
+    *left.project_to(fields.player.health) = 100;
+
+    assert_eq!(world.tick, 10);
+    assert_eq!(world.positions, [1.0]);
+    assert_eq!(world.scores, [2]);
+    assert_eq!(world.player.health, 100);
+    assert_eq!(world.player.name, "");
+}
+
+#[test]
+fn threads() {
+    let fields = World::fields();
+    let mut world = World {
+        positions: vec![0.0; 4],
+        scores:    vec![0; 4],
+        ..World::default()
+    };
+
+    let list = Disjoint::new(hlist!(fields.positions, fields.scores));
+    let partial = Partial::new(&mut world, list);
+    let (mut physics, mut scoring) = partial.split(hlist!(fields.positions));
+
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            for position in physics.get_mut(fields.positions) {
+                *position += 1.5;
+            }
+        });
+
+        scope.spawn(move || {
+            for score in scoring.get_mut(fields.scores) {
+                *score += 2;
+            }
+        });
+    });
+
+    assert_eq!(world.positions, [1.5; 4]);
+    assert_eq!(world.scores, [2; 4]);
+}
+
+#[test]
+#[should_panic(expected = "Found overlapping fields")]
+fn overlapping() {
+    let fields = World::fields();
+    let mut world = World::default();
+
+    let list = Disjoint::new(hlist!(fields.player, fields.player.health));
+    let _ = Partial::new(&mut world, list);
+}
+
+#[test]
+#[should_panic(expected = "The field is not the one in the `Partial`")]
+fn wrong_dynamic_field() {
+    let fields = Player::fields();
+    let mut player = Player::default();
+
+    // both fields are `Dynamic<Player, u8>`, so they can't be told apart by
+    // their type
+    let health = fields.health.dynamic();
+    let other = unsafe { Dynamic::from_offset(0) };
+
+    let list = Disjoint::new(hlist!(health));
+    let partial = Partial::new(&mut player, list);
+    assert_eq!(*partial.get(health), 0);
+
+    let _: &u8 = partial.get(other);
+}
+
+#[derive(Default, Field)]
+struct Rgb {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[test]
+#[should_panic(expected = "The fields are not the ones in the `Partial`")]
+fn split_wrong_dynamic_field() {
+    let fields = Rgb::fields();
+    let mut rgb = Rgb::default();
+
+    // `r` and `g` are both `Dynamic<Rgb, u8>`, so `g` finds the stored `r`
+    let r = fields.r.dynamic();
+    let g = fields.g.dynamic();
+
+    let list = Disjoint::new(hlist!(r, fields.b));
+    let mut partial = Partial::new(&mut rgb, list);
+    let (mut red, _) = partial.reborrow().split(hlist!(r));
+    *red.get_mut(r) = 1;
+
+    let _ = partial.split(hlist!(g));
+}
@@ -0,0 +1,177 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::{Dynamic, Field, PathError, ProjectAll, ProjectTo, Reflect};
+use typsy::convert::Convert;
+
+use std::{any::TypeId, mem};
+
+#[derive(Field)]
+struct Foo {
+    x:    u8,
+    y:    u32,
+    name: String,
+    skip: NoDebug,
+}
+
+struct NoDebug;
+
+#[derive(Field)]
+struct Pair<T>(T, u16);
+
+#[test]
+fn table() {
+    let info = Foo::field_info();
+
+    let names: Vec<_> = info.iter().map(|info| info.name()).collect();
+    assert_eq!(names, ["x", "y", "name", "skip"]);
+
+    let y = Foo::find_field("y").unwrap();
+    assert_eq!(y.offset(), Foo::fields().y.field_offset());
+    assert_eq!(y.size(), 4);
+    assert_eq!(y.align(), mem::align_of::<u32>());
+    assert_eq!(y.type_id(), Some(TypeId::of::<u32>()));
+    assert_eq!(y.type_name(), "u32");
+
+    assert!(Foo::find_field("z").is_none());
+}
+
+#[test]
+fn generic_table() {
+    let info = Pair::<u64>::field_info();
+
+    assert_eq!(info[0].name(), "0");
+    assert_eq!(info[0].size(), 8);
+    assert_eq!(info[0].type_id(), None);
+    assert_eq!(info[1].offset(), Pair::<u64>::fields().1.field_offset());
+}
+
+#[test]
+fn dynamic() {
+    let foo = Foo {
+        x:    1,
+        y:    2,
+        name: String::from("foo"),
+        skip: NoDebug,
+    };
+
+    let info = Foo::find_field("name").unwrap();
+
+    assert!(Foo::dynamic::<u32>(info).is_none());
+    assert!(Pair::<u64>::dynamic::<String>(info).is_none());
+
+    let name = Foo::dynamic::<String>(info).unwrap();
+    assert_eq!(foo.project_to(name), "foo");
+}
+
+#[test]
+fn visit() {
+    let mut foo = Foo {
+        x:    1,
+        y:    2,
+        name: String::from("foo"),
+        skip: NoDebug,
+    };
+
+    let mut debug = Vec::new();
+    foo.visit_debug(|info, value| {
+        debug.push(format!("{}: {:?}", info.name(), value))
+    });
+    assert_eq!(debug, ["x: 1", "y: 2", "name: \"foo\""]);
+
+    let mut count = 0;
+    foo.visit_any(|_, _| count += 1);
+    assert_eq!(count, 4);
+
+    foo.visit_any_mut(|_, value| {
+        if let Some(y) = value.downcast_mut::<u32>() {
+            *y += 10;
+        }
+    });
+    assert_eq!(foo.y, 12);
+
+    // fields that aren't `'static` are never visited as `Any`
+    let pair = Pair(0_u64, 3);
+    pair.visit_any(|_, _| panic!());
+
+    let mut debug = Vec::new();
+    pair.visit_debug(|info, value| {
+        debug.push(format!("{}: {:?}", info.name(), value))
+    });
+    assert_eq!(debug, ["1: 3"]);
+}
+
+#[derive(Default, Field)]
+struct Outer {
+    a: u8,
+    y: Middle,
+}
+
+#[derive(Default, Field)]
+struct Middle {
+    b: u16,
+    c: Inner,
+}
+
+#[derive(Default, Field)]
+struct Inner {
+    q: u32,
+    r: u64,
+}
+
+#[test]
+fn path() {
+    let mut outer = Outer {
+        a: 1,
+        y: Middle {
+            b: 2,
+            c: Inner {
+                q: 3,
+                r: 4,
+            },
+        },
+    };
+
+    let r = Dynamic::<Outer, u64>::from_path("y.c.r").unwrap();
+    assert_eq!(*outer.project_to(r), 4);
+
+    let a = Dynamic::<Outer, u8>::from_name("a").unwrap();
+    assert_eq!(*outer.project_to(a), 1);
+
+    let q = Dynamic::<Outer, u32>::from_path("y.c.q").unwrap();
+    let typsy::hlist_pat!(q, r) = (&mut outer).project_all((q, r).into_hlist());
+    *q += *r as u32;
+    assert_eq!(outer.y.c.q, 7);
+
+    assert_eq!(
+        Dynamic::<Outer, u64>::from_path("y.d.r").err(),
+        Some(PathError::UnknownField("d"))
+    );
+    assert_eq!(
+        Dynamic::<Outer, u64>::from_path("a.b").err(),
+        Some(PathError::UnknownField("b"))
+    );
+    assert_eq!(
+        Dynamic::<Outer, u8>::from_path("y.c.r").err(),
+        Some(PathError::TypeMismatch {
+            field:    "r",
+            expected: "u8",
+            found:    "u64",
+        })
+    );
+    assert_eq!(
+        Dynamic::<Pair<u8>, u16>::from_name("1").err(),
+        Some(PathError::Untyped("1"))
+    );
+}
+
+#[test]
+#[should_panic]
+fn path_overlap() {
+    let mut outer = Outer::default();
+
+    let c = Dynamic::<Outer, Inner>::from_path("y.c").unwrap();
+    let r = Dynamic::<Outer, u64>::from_path("y.c.r").unwrap();
+
+    (&mut outer).project_all((c, r).into_hlist());
+}
@@ -0,0 +1,96 @@
+#![feature(raw_ref_op)]
+#![allow(clippy::blacklisted_name)]
+
+use gfp_core::*;
+
+#[derive(Clone, Copy, Field)]
+#[field(pod)]
+#[repr(C)]
+struct Pair {
+    a: u32,
+    b: u32,
+}
+
+#[derive(Clone, Copy, Field)]
+#[field(pod)]
+#[repr(C)]
+struct Graph {
+    pairs: [Pair; 2],
+    link:  RelPtr<Pair>,
+}
+
+#[test]
+fn resolve() {
+    let mut graph = Graph {
+        pairs: [
+            Pair {
+                a: 1,
+                b: 2,
+            },
+            Pair {
+                a: 3,
+                b: 4,
+            },
+        ],
+        link:  RelPtr::NULL,
+    };
+
+    assert!(graph.link.as_ptr().is_null());
+
+    let target = &graph.pairs[1] as *const Pair;
+    graph.link.set(target);
+
+    assert!(graph.link.offset() < 0);
+    assert_eq!(graph.link.as_ptr(), target);
+    assert_eq!(unsafe { graph.link.as_ref() }.map(|pair| pair.a), Some(3));
+
+    // moving the whole graph keeps the link valid
+    let moved = Box::new(graph);
+    assert_eq!(unsafe { moved.link.as_ref() }.map(|pair| pair.b), Some(4));
+}
+
+#[test]
+fn project() {
+    let mut graph = Graph {
+        pairs: [Pair {
+            a: 1,
+            b: 2,
+        }; 2],
+        link:  RelPtr::NULL,
+    };
+
+    let target = &graph.pairs[0] as *const Pair;
+    graph.link.set(target);
+
+    let link_b = graph.link.project_to(Pair::fields().b);
+
+    assert_eq!(link_b.offset(), graph.link.offset() + 4);
+    assert_eq!(unsafe { *link_b.resolve_from(&graph.link) }, 2);
+
+    let null = RelPtr::<Pair>::NULL.project_to(Pair::fields().b);
+    assert!(null.is_null());
+}
+
+#[derive(Clone, Copy, Field)]
+#[field(pod)]
+#[repr(C)]
+struct Node {
+    value: u64,
+    next:  RelPtr<Node>,
+}
+
+#[test]
+#[should_panic(expected = "Projected a `RelPtr` to the location it is")]
+fn project_to_self() {
+    let mut node = Node {
+        value: 1,
+        next:  RelPtr::NULL,
+    };
+
+    let target = &node as *const Node;
+    node.next.set(target);
+    assert_eq!(unsafe { node.next.as_ref() }.map(|node| node.value), Some(1));
+
+    // `next` is the `RelPtr` itself, so its offset from itself is zero
+    let _ = node.next.project_to(Node::fields().next);
+}
@@ -0,0 +1,110 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{check_field, Field, FieldError, ProjectAll, ProjectTo};
+
+use std::ops::Range;
+
+mod foreign {
+    #[derive(Debug, Default, PartialEq)]
+    pub struct Config {
+        pub name:    String,
+        pub retries: u32,
+        private:     bool,
+    }
+
+    #[derive(Default)]
+    #[repr(C, packed)]
+    pub struct Packed {
+        pub tag:   u8,
+        pub value: u32,
+    }
+}
+
+gfp_core::remote_fields! {
+    mod range_fields for Range<u32> {
+        start: u32,
+        end: u32
+    }
+}
+
+gfp_core::remote_fields! {
+    /// Only the public fields of `Config`
+    pub(crate) mod config_fields for foreign::Config {
+        name: String,
+        retries: u32,
+        ..
+    }
+}
+
+gfp_core::remote_fields! {
+    mod packed_fields for foreign::Packed {
+        tag: u8,
+        value: u32,
+    }
+}
+
+#[test]
+fn range() {
+    let fields = range_fields::fields();
+    let mut range = 3..7;
+
+    let typsy::hlist_pat!(start, end) =
+        (&mut range).project_all(typsy::hlist!(fields.start, fields.end));
+    *start += 1;
+    *end -= 1;
+
+    assert_eq!(range, 4..6);
+    assert_eq!(fields.end.field_offset(), {
+        let range = 0..0_u32;
+        &range.end as *const u32 as usize - &range as *const _ as usize
+    });
+}
+
+#[test]
+fn partial() {
+    let mut config = foreign::Config::default();
+
+    *(&mut config).project_to(config_fields::FIELDS.name) = "gfp".into();
+    *(&mut config).project_to(config_fields::FIELDS.retries) += 3;
+
+    assert_eq!(config.name, "gfp");
+    assert_eq!(config.retries, 3);
+}
+
+#[test]
+fn packed() {
+    let fields = packed_fields::fields();
+    let mut packed = foreign::Packed::default();
+
+    // Safety: the field is read and written without creating a reference
+    unsafe {
+        let value = fields.value.project_raw_mut(&mut packed);
+        value.write_unaligned(42);
+    }
+
+    assert_eq!({ packed.value }, 42);
+    assert_eq!(fields.tag.range(), 0..1);
+    assert_eq!(fields.value.range(), 1..5);
+    assert_eq!(fields.value.dynamic().offset(), 1);
+
+    // fields of packed structs are expected to be misaligned
+    assert_eq!(check_field(&fields.tag), Ok(()));
+    assert_eq!(
+        check_field(&fields.value),
+        Err(FieldError::Misaligned {
+            offset: 1,
+            align:  4,
+        })
+    );
+}
+
+#[test]
+fn valid() {
+    let range = range_fields::fields();
+    let config = config_fields::fields();
+
+    assert_eq!(check_field(&range.start), Ok(()));
+    assert_eq!(check_field(&range.end), Ok(()));
+    assert_eq!(check_field(&config.name), Ok(()));
+    assert_eq!(check_field(&config.retries), Ok(()));
+}
@@ -0,0 +1,80 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{clone_fields, copy_fields, Field};
+use typsy::hlist;
+
+#[derive(Debug, Default, Clone, PartialEq, Field)]
+struct Config {
+    name:    String,
+    retries: u32,
+    tags:    Vec<String>,
+    limits:  Limits,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Field)]
+struct Limits {
+    max: u64,
+    min: u64,
+}
+
+fn config(name: &str) -> Config {
+    Config {
+        name:    name.to_string(),
+        retries: 3,
+        tags:    vec![name.to_string()],
+        limits:  Limits {
+            max: 10,
+            min: 1,
+        },
+    }
+}
+
+#[test]
+fn replace_and_take() {
+    let fields = Config::fields();
+    let mut config = config("a");
+
+    assert_eq!(fields.retries.replace(&mut config, 5), 3);
+    assert_eq!(fields.limits.max.replace(&mut config, 20), 10);
+    assert_eq!(fields.tags.take(&mut config), ["a"]);
+    assert_eq!(fields.name.take(&mut config), "a");
+
+    assert_eq!(config, Config {
+        retries: 5,
+        limits:  Limits {
+            max: 20,
+            min: 1,
+        },
+        ..Config::default()
+    });
+}
+
+#[test]
+fn swap() {
+    let fields = Config::fields();
+    let mut a = config("a");
+    let mut b = config("b");
+    b.limits.min = 2;
+
+    fields.name.swap(&mut a, &mut b);
+    fields.limits.min.swap(&mut a, &mut b);
+
+    assert_eq!((a.name.as_str(), a.tags[0].as_str()), ("b", "a"));
+    assert_eq!((b.name.as_str(), b.tags[0].as_str()), ("a", "b"));
+    assert_eq!((a.limits.min, b.limits.min), (2, 1));
+}
+
+#[test]
+fn clone_field_from() {
+    let fields = Config::fields();
+    let src = config("src");
+    let mut dst = Config::default();
+
+    fields.tags.clone_field_from(&mut dst, &src);
+    assert_eq!(dst.tags, ["src"]);
+    assert_eq!(dst.name, "");
+
+    clone_fields(&mut dst, &src, hlist!(fields.name, fields.limits));
+    copy_fields(&mut dst, &src, hlist!(fields.retries));
+    assert_eq!(dst, src);
+}
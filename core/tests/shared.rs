@@ -0,0 +1,130 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{Disjoint, Field, Partial, ProjectAll, Shared, Unique};
+use typsy::hlist;
+
+#[derive(Default, Field)]
+struct Message {
+    header: Header,
+    body:   Vec<u8>,
+}
+
+#[derive(Default, Field)]
+struct Header {
+    len:   usize,
+    flags: u8,
+}
+
+#[test]
+fn mixed() {
+    let fields = Message::fields();
+    let mut message = Message::default();
+    message.header.len = 2;
+
+    let typsy::hlist_pat!(header, len, body, flags) =
+        (&mut message).project_all(hlist!(
+            Shared(fields.header),
+            Shared(fields.header.len),
+            fields.body,
+            Shared::new(fields.header.flags),
+        ));
+
+    body.resize(*len, 7);
+    assert_eq!(header.len, 2);
+    assert_eq!(*flags, 0);
+    assert_eq!(message.body, [7, 7]);
+}
+
+#[test]
+fn unique_and_shared() {
+    let fields = Message::fields();
+    let mut message = Message::default();
+
+    let list = Disjoint::new(hlist!(
+        Unique(fields.header.flags),
+        Shared(fields.header.len),
+        Shared(fields.body),
+    ));
+
+    for _ in 0..3 {
+        let typsy::hlist_pat!(flags, len, body) =
+            (&mut message).project_all(list);
+        *flags += 1;
+        assert_eq!((*len, body.len()), (0, 0));
+    }
+
+    let typsy::hlist_pat!(header, body) = (&message)
+        .project_all(hlist!(Shared(fields.header), Unique(fields.body)));
+    assert_eq!(header.flags, 3);
+    assert!(body.is_empty());
+
+    assert_eq!(message.header.flags, 3);
+}
+
+#[test]
+fn partial() {
+    let fields = Message::fields();
+    let mut message = Message::default();
+
+    let list = hlist!(Shared(fields.header), fields.body);
+    let partial = Partial::new(&mut message, Disjoint::new(list));
+    let typsy::hlist_pat!(header, body) = partial.project_all();
+    body.push(header.flags);
+
+    assert_eq!(message.body, [0]);
+}
+
+#[test]
+fn threads() {
+    let fields = Message::fields();
+    let mut message = Message::default();
+    message.header.len = 3;
+
+    let list = hlist!(
+        Shared(fields.header),
+        Shared(fields.header.len),
+        fields.body,
+    );
+    let partial = Partial::new(&mut message, Disjoint::new(list));
+    let (reader, writer) = partial.split(hlist!(Shared(fields.header)));
+
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            let typsy::hlist_pat!(header) = reader.project_all();
+            assert_eq!(header.len, 3);
+        });
+
+        let typsy::hlist_pat!(len, body) = writer.project_all();
+        body.resize(*len, 1);
+    });
+
+    assert_eq!(message.body, [1, 1, 1]);
+}
+
+#[test]
+#[should_panic(expected = "Found overlapping fields")]
+fn shared_and_unique_overlap() {
+    let fields = Message::fields();
+    let mut message = Message::default();
+
+    let _ = (&mut message).project_all(hlist!(
+        Shared(fields.header),
+        Unique(fields.header.len),
+    ));
+}
+
+#[test]
+fn try_new() {
+    let fields = Message::fields();
+
+    assert!(Disjoint::try_new(hlist!(
+        Shared(fields.header),
+        Shared(fields.header.len),
+    ))
+    .is_some());
+    assert!(Disjoint::try_new(hlist!(
+        fields.header.len,
+        Shared(fields.header),
+    ))
+    .is_none());
+}
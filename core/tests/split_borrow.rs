@@ -0,0 +1,96 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::{BorrowError, Field, SplitBorrow, SplitMut};
+
+#[derive(Default, Field)]
+#[repr(C)]
+struct State {
+    count:  u32,
+    inner:  Inner,
+    marker: [u8; 0],
+    log:    Vec<&'static str>,
+}
+
+#[derive(Debug, Default, Field)]
+#[repr(C)]
+struct Inner {
+    a: u16,
+    b: u16,
+}
+
+#[test]
+fn incremental() {
+    let fields = State::fields();
+    let inner = Inner::fields();
+    let mut state = State::default();
+
+    let split = SplitBorrow::new(&mut state);
+
+    let mut log = split.borrow_mut(fields.log);
+    let mut count = split.borrow_mut(fields.count);
+    log.push("count");
+    *count += 1;
+
+    if *count == 1 {
+        let mut a = split.borrow_mut(fields.inner.chain(inner.a));
+        *a = 2;
+        log.push("a");
+    }
+
+    let mut b = split.borrow_mut(fields.inner.chain(inner.b));
+    *b = 3;
+
+    assert_eq!(split.borrowed().len(), 3);
+    drop((log, count, b));
+    assert!(split.borrowed().is_empty());
+
+    assert_eq!(state.count, 1);
+    assert_eq!((state.inner.a, state.inner.b), (2, 3));
+    assert_eq!(state.log, ["count", "a"]);
+}
+
+#[test]
+fn overlapping() {
+    let fields = State::fields();
+    let inner = Inner::fields();
+    let mut state = State::default();
+
+    let split = SplitBorrow::new(&mut state);
+    let a = split.borrow_mut(fields.inner.chain(inner.a));
+
+    assert_eq!(
+        split.try_borrow_mut(fields.inner).unwrap_err(),
+        BorrowError {
+            requested: 4..8,
+            borrowed:  4..6,
+        }
+    );
+    assert_eq!(SplitMut::range(&a), 4..6);
+
+    drop(a);
+    assert!(split.try_borrow_mut(fields.inner).is_ok());
+}
+
+#[test]
+fn zero_sized() {
+    let fields = State::fields();
+    let mut state = State::default();
+
+    let split = SplitBorrow::new(&mut state);
+    let first = split.borrow_mut(fields.marker);
+    let second = split.borrow_mut(fields.marker);
+
+    assert!(first.is_empty() && second.is_empty());
+    assert!(split.borrowed().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "overlaps the borrowed field")]
+fn twice() {
+    let fields = State::fields();
+    let mut state = State::default();
+
+    let split = SplitBorrow::new(&mut state);
+    let _count = split.borrow_mut(fields.count);
+    let _count = split.borrow_mut(fields.count);
+}
@@ -0,0 +1,80 @@
+#![feature(raw_ref_op)]
+
+use gfp_core::Field;
+
+use std::{marker::PhantomPinned, pin::Pin};
+
+#[derive(Default, Field)]
+#[field(views, view(Motion(position, velocity), Label(name)))]
+struct Particle {
+    name:     String,
+    position: f32,
+    velocity: f32,
+}
+
+#[derive(Default, Field)]
+#[field(views, view(Second(1)))]
+struct Pair<T>(T, u8);
+
+#[derive(Default, Field)]
+#[field(views)]
+struct Task {
+    #[field(pin)]
+    state: PhantomPinned,
+    polls: u32,
+}
+
+fn step(motion: MotionMut<'_>) {
+    *motion.position += *motion.velocity;
+}
+
+#[test]
+fn named() {
+    let mut particle = Particle {
+        name:     String::from("a"),
+        position: 1.0,
+        velocity: 2.0,
+    };
+
+    let view = particle.split_mut();
+    view.name.push('b');
+    *view.position += *view.velocity;
+
+    let view = particle.split();
+    assert_eq!(view.name, "ab");
+    assert_eq!(*view.position, 3.0);
+
+    step(MotionMut::from(&mut particle));
+    assert_eq!(particle.position, 5.0);
+    assert_eq!(particle.velocity, 2.0);
+
+    let label = LabelRef::from(&particle);
+    assert_eq!(label.name, "ab");
+}
+
+#[test]
+fn tuple() {
+    let mut pair = Pair(String::from("x"), 1);
+
+    let PairMut(first, second) = pair.split_mut();
+    first.push('y');
+    *second += 1;
+
+    let second = SecondRef::from(&pair);
+    assert_eq!(*second.0, 2);
+    assert_eq!(pair.0, "xy");
+}
+
+#[test]
+fn pinned() {
+    let mut task = Box::pin(Task::default());
+
+    let view = task.as_mut().split_pin();
+    let _: Pin<&mut PhantomPinned> = view.state;
+    *view.polls += 1;
+
+    let view = Pin::as_mut(&mut task).split_pin();
+    *view.polls += 1;
+
+    assert_eq!(task.polls, 2);
+}
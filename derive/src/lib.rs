@@ -19,10 +19,122 @@ use quote::quote;
 /// For `struct`, getting the field types is safe since the memory operations
 /// defined use raw pointers to initialized fields so UB is not possible.
 ///
+/// The derive also implements `gfp_core::Reflect`, which gives a runtime table
+/// of every field. Union fields are listed, but can't be visited.
+///
+/// Generic code can get the field types from `gfp_core::HasFields`, or
+/// `gfp_core::HasUnionFields` for unions, which the derive implements. Struct
+/// fields can also be found by name with `gfp_core::HasField`, or by position
+/// with `gfp_core::FieldAt`.
+///
 ///  * note: unit structs don't generate any extra code (i.e. `struct Foo;`)
 ///
 /// `enums` are not supported.
 ///
+/// # Options
+///
+/// Options can be passed to the derive with a `#[field(...)]` attribute on the
+/// type
+///
+///  * `#[field(pod)]` implements `gfp_core::Pod` for the type, this requires
+///    every field to be `Pod`. This is not supported for `repr(packed)` types
+///  * `#[field(as_bytes)]` implements `gfp_core::AsBytes` for the type, this
+///    requires every field to be `AsBytes`, and the type to have no padding.
+///    This is not supported for `union`s
+///  * `#[field(no_padding)]` fails to compile if the type has any padding
+///    between or after its fields. Padding inside the fields is not checked,
+///    `as_bytes` checks that too. This is not supported for `union`s
+///  * `#[field(views)]` generates the views `{$type}Ref`, `{$type}Mut`, and
+///    `{$type}Pin` with a reference to every field, which are created by the
+///    `split`, `split_mut`, and `split_pin` methods. This is not supported for
+///    `union`s
+///  * `#[field(view(Name(fields...)))]` generates the views `NameRef`,
+///    `NameMut`, and `NamePin` of only the given fields, which are created with
+///    `From`
+///  * `#[field(copy_from(Types...))]` implements `gfp_core::CopyNamed` for each
+///    of the types, which copies every field from the field of the same name
+///    in the other type, and converts it with `From`. The other types must
+///    also derive `Field`. This is not supported for `union`s
+///
+/// And with a `#[field(...)]` attribute on a field
+///
+///  * `#[field(offset = N)]` fails to compile if the field is not at offset `N`
+///  * `#[field(size = N)]` fails to compile if the field is not `N` bytes
+///  * `#[field(pin)]` pins the field in the pinned views, other fields are
+///    given as `&mut`. The type may not implement `Drop`, and is only `Unpin`
+///    if all of the pinned fields are `Unpin`
+///  * `#[field(skip_copy)]` leaves the field as it is in
+///    `gfp_core::CopyNamed`, otherwise a field that is missing from the other
+///    type fails to compile
+///
+/// These layout assertions are not supported for generic types.
+///
+/// ```
+/// # #![feature(raw_ref_op)]
+/// # mod test {
+/// # use gfp_core::Field;
+/// #[derive(Field)]
+/// #[field(views, view(Motion(position, velocity)))]
+/// struct Particle {
+///     name: String,
+///     position: f32,
+///     velocity: f32,
+/// }
+///
+/// fn step(motion: MotionMut<'_>) {
+///     *motion.position += *motion.velocity;
+/// }
+///
+/// fn update(particle: &mut Particle) {
+///     let view = particle.split_mut();
+///     view.name.clear();
+///     *view.velocity *= 0.5;
+///
+///     step(MotionMut::from(particle));
+/// }
+/// # }
+/// ```
+///
+/// ```
+/// # #![feature(raw_ref_op)]
+/// # mod test {
+/// # use gfp_core::Field;
+/// #[derive(Field)]
+/// #[field(no_padding)]
+/// #[repr(C)]
+/// struct Header {
+///     #[field(offset = 0, size = 2)]
+///     tag: u16,
+///     #[field(offset = 2, size = 2)]
+///     len: u16,
+///     #[field(offset = 4)]
+///     crc: u32,
+/// }
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #![feature(raw_ref_op)]
+/// # mod test {
+/// # use gfp_core::Field;
+/// #[derive(Field)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u16,
+///     #[field(offset = 2)]
+///     crc: u32,
+/// }
+/// # }
+/// ```
+///
+/// # Nested fields
+///
+/// The field types of a struct `Deref` to the field types of their own type,
+/// if that type also derives `Field`. So `Foo::fields().bar.tap` is the same
+/// as `Foo::fields().bar.chain(Bar::fields().tap)`. Fields with the type of a
+/// type parameter don't get this. See `field_path!` to name these nested
+/// field types.
+///
 /// For example for a struct,
 /// ```
 /// # #![feature(raw_ref_op)]
@@ -36,8 +148,9 @@ use quote::quote;
 /// }
 /// # }
 /// ```
-/// will generate (excluding comments) the following. Note the use of `&raw `,
-/// this is to allow safe projection through raw pointers.
+/// will generate (excluding comments, nested fields, and `Reflect`) the
+/// following. Note the use of `&raw `, this is to allow safe projection through
+/// raw pointers.
 /// ```
 /// # #![feature(raw_ref_op)]
 /// # mod test {
@@ -46,7 +159,8 @@ use quote::quote;
 ///     age: u16,
 ///     children: Vec<Person>,
 /// }
-/// // This struct holds all of the field types for easy access
+/// // This struct holds all of the field types for easy access, it has the
+/// // same visibility as `Person` because it's used by the public trait impls
 /// struct PersonFields {
 ///     name: Person_fields::name<Person>,
 ///     age: Person_fields::age<Person>,
@@ -82,86 +196,107 @@ use quote::quote;
 ///     use super::*;
 ///     // represents the `name` field of `Person`
 ///     #[allow(non_camel_case_types)]
-///     pub struct name<T>(::gfp_core::derive::Invariant<T>);
-///     impl<T> name<T> {
+///     pub struct name<T, P = ::gfp_core::Identity<T>>(::gfp_core::derive::Invariant<(T, P)>);
+///     impl<T, P> name<T, P> {
 ///         pub const INIT: Self = Self(::gfp_core::derive::Invariant::INIT);
 ///     }
-///     impl<T> Clone for name<T> {
+///     impl<T, P> Clone for name<T, P> {
 ///         fn clone(&self) -> Self {
 ///             *self
 ///         }
 ///     }
-///     impl<T> Copy for name<T> {}
-///     unsafe impl ::gfp_core::Field for name<super::Person> {
-///         type Parent = super::Person;
+///     impl<T, P> Copy for name<T, P> {}
+///     // `P` is the field that this field is nested in
+///     unsafe impl<P> ::gfp_core::Field for name<super::Person, P>
+///     where
+///         P: ::gfp_core::derive::Prefix<Type = super::Person>,
+///     {
+///         type Parent = P::Parent;
 ///         type Type = String;
 ///         #[inline]
 ///         unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+///             let ptr = P::INIT.project_raw(ptr);
 ///             &raw const (*ptr).name
 ///         }
 ///         #[inline]
 ///         unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+///             let ptr = P::INIT.project_raw_mut(ptr);
 ///             &raw mut (*ptr).name
 ///         }
 ///     }
 ///     // represents the `age` field of `Person`
 ///     #[allow(non_camel_case_types)]
-///     pub struct age<T>(::gfp_core::derive::Invariant<T>);
-///     impl<T> age<T> {
+///     pub struct age<T, P = ::gfp_core::Identity<T>>(::gfp_core::derive::Invariant<(T, P)>);
+///     impl<T, P> age<T, P> {
 ///         pub const INIT: Self = Self(::gfp_core::derive::Invariant::INIT);
 ///     }
-///     impl<T> Clone for age<T> {
+///     impl<T, P> Clone for age<T, P> {
 ///         fn clone(&self) -> Self {
 ///             *self
 ///         }
 ///     }
-///     impl<T> Copy for age<T> {}
-///     unsafe impl ::gfp_core::Field for age<super::Person> {
-///         type Parent = super::Person;
+///     impl<T, P> Copy for age<T, P> {}
+///     unsafe impl<P> ::gfp_core::Field for age<super::Person, P>
+///     where
+///         P: ::gfp_core::derive::Prefix<Type = super::Person>,
+///     {
+///         type Parent = P::Parent;
 ///         type Type = u16;
 ///         #[inline]
 ///         unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+///             let ptr = P::INIT.project_raw(ptr);
 ///             &raw const (*ptr).age
 ///         }
 ///         #[inline]
 ///         unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+///             let ptr = P::INIT.project_raw_mut(ptr);
 ///             &raw mut (*ptr).age
 ///         }
 ///     }
 ///     // represents the `children` field of `Person`
 ///     #[allow(non_camel_case_types)]
-///     pub struct children<T>(::gfp_core::derive::Invariant<T>);
-///     impl<T> children<T> {
+///     pub struct children<T, P = ::gfp_core::Identity<T>>(::gfp_core::derive::Invariant<(T, P)>);
+///     impl<T, P> children<T, P> {
 ///         pub const INIT: Self = Self(::gfp_core::derive::Invariant::INIT);
 ///     }
-///     impl<T> Clone for children<T> {
+///     impl<T, P> Clone for children<T, P> {
 ///         fn clone(&self) -> Self {
 ///             *self
 ///         }
 ///     }
-///     impl<T> Copy for children<T> {}
-///     unsafe impl ::gfp_core::Field for children<super::Person> {
-///         type Parent = super::Person;
+///     impl<T, P> Copy for children<T, P> {}
+///     unsafe impl<P> ::gfp_core::Field for children<super::Person, P>
+///     where
+///         P: ::gfp_core::derive::Prefix<Type = super::Person>,
+///     {
+///         type Parent = P::Parent;
 ///         type Type = Vec<Person>;
 ///         #[inline]
 ///         unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+///             let ptr = P::INIT.project_raw(ptr);
 ///             &raw const (*ptr).children
 ///         }
 ///         #[inline]
 ///         unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+///             let ptr = P::INIT.project_raw_mut(ptr);
 ///             &raw mut (*ptr).children
 ///         }
 ///     }
 /// }
 /// # }
 /// ```
-#[proc_macro_derive(Field)]
+#[proc_macro_derive(Field, attributes(field))]
 pub fn derive_field(ty: TokenStream) -> TokenStream {
     let ty = syn::parse_macro_input!(ty as syn::DeriveInput);
 
+    let options = match Options::parse(&ty.attrs) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
     match ty.data {
-        syn::Data::Struct(_) => derive_struct(ty),
-        syn::Data::Union(_) => derive_union(ty),
+        syn::Data::Struct(_) => derive_struct(ty, options),
+        syn::Data::Union(_) => derive_union(ty, options),
         syn::Data::Enum(_) => {
             syn::Error::new(ty.ident.span(), "enums are not supported")
                 .to_compile_error()
@@ -170,6 +305,148 @@ pub fn derive_field(ty: TokenStream) -> TokenStream {
     }
 }
 
+/// Get a nested field type from a path of fields, like `Foo.bar.tap`
+///
+/// The path starts with the type, followed by the names of the fields, which
+/// can be indices for tuple structs. Every type in the path must derive
+/// `Field`. This can be used in a `const`, and the type can be named with
+/// `FieldPath!`.
+///
+/// ```rust
+/// #![feature(raw_ref_op)]
+/// # mod main {
+/// use gfp_core::{field_path, Field, FieldPath, ProjectTo};
+///
+/// #[derive(Field)]
+/// struct Foo {
+///     bar: Bar,
+/// }
+///
+/// #[derive(Field)]
+/// struct Bar(u8, Tap);
+///
+/// #[derive(Field)]
+/// struct Tap {
+///     val: u32,
+/// }
+///
+/// const FOO_TO_VAL: FieldPath!(Foo.bar.1.val) = field_path!(Foo.bar.1.val);
+///
+/// fn main() {
+///     let foo = Foo {
+///         bar: Bar(0, Tap { val: 10 }),
+///     };
+///
+///     assert_eq!(*(&foo).project_to(FOO_TO_VAL), 10);
+///     assert_eq!(*(&foo).project_to(Foo::fields().bar.1.val), 10);
+/// }
+/// # }
+/// ```
+#[proc_macro]
+pub fn field_path(path: TokenStream) -> TokenStream {
+    let path = syn::parse_macro_input!(path as FieldPath);
+    let ty = path.to_type();
+
+    TokenStream::from(quote!(
+        <#ty as ::gfp_core::derive::Prefix>::INIT
+    ))
+}
+
+/// Name the type of a nested field from a path of fields, like `Foo.bar.tap`
+///
+/// See `field_path!` for more details
+#[allow(non_snake_case)]
+#[proc_macro]
+pub fn FieldPath(path: TokenStream) -> TokenStream {
+    let path = syn::parse_macro_input!(path as FieldPath);
+
+    TokenStream::from(path.to_type())
+}
+
+/// Name a field in the type system, for `gfp_core::HasField`
+///
+/// This takes the name of a field, or the index of a field of a tuple struct
+///
+/// ```rust
+/// # #![feature(raw_ref_op)]
+/// # mod test {
+/// use gfp_core::{field_name, Field, HasField};
+///
+/// #[derive(Field)]
+/// struct Foo(u32, String);
+///
+/// fn second<T: HasField<field_name!(1)>>() -> T::Field {
+///     T::FIELD
+/// }
+///
+/// # fn test() {
+/// let field = second::<Foo>();
+/// # }
+/// # }
+/// ```
+#[proc_macro]
+pub fn field_name(name: TokenStream) -> TokenStream {
+    let member = syn::parse_macro_input!(name as syn::Member);
+
+    TokenStream::from(field_name_type(&member_name(&member)))
+}
+
+/// A path of fields, like `Foo.bar.tap`
+struct FieldPath {
+    root:    syn::Type,
+    members: Vec<syn::Member>,
+}
+
+impl syn::parse::Parse for FieldPath {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let root = input.parse()?;
+        let mut members = Vec::new();
+
+        while !input.is_empty() {
+            input.parse::<syn::Token![.]>()?;
+
+            // `0.1` is a single float literal
+            if input.peek(syn::LitFloat) {
+                let float = input.parse::<syn::LitFloat>()?;
+
+                for index in float.base10_digits().split('.') {
+                    members.push(syn::Member::Unnamed(syn::Index {
+                        index: index.parse().map_err(|_| {
+                            syn::Error::new(float.span(), "expected a field")
+                        })?,
+                        span:  float.span(),
+                    }));
+                }
+            } else {
+                members.push(input.parse()?);
+            }
+        }
+
+        if members.is_empty() {
+            return Err(input.error("expected a field"))
+        }
+
+        Ok(Self {
+            root,
+            members,
+        })
+    }
+}
+
+impl FieldPath {
+    fn to_type(&self) -> proc_macro2::TokenStream {
+        let root = &self.root;
+        let mut ty = quote!(::gfp_core::Identity<#root>);
+
+        for member in &self.members {
+            let name = field_name_type(&member_name(member));
+            ty = quote!(<#ty as ::gfp_core::derive::Step<#name>>::Next);
+        }
+
+        ty
+    }
+}
+
 macro_rules! item {
     ($($tokens:tt)*) => {{
         let quote = TokenStream::from(quote!($($tokens)*));
@@ -184,16 +461,16 @@ macro_rules! expr {
     }}
 }
 
-fn derive_struct(ty: syn::DeriveInput) -> TokenStream {
+fn derive_struct(ty: syn::DeriveInput, options: Options) -> TokenStream {
     match ty.data {
         syn::Data::Struct(syn::DataStruct {
             fields: syn::Fields::Named(_),
             ..
-        }) => derive_named(ty),
+        }) => derive_named(ty, options),
         syn::Data::Struct(syn::DataStruct {
             fields: syn::Fields::Unnamed(_),
             ..
-        }) => derive_unnamed(ty),
+        }) => derive_unnamed(ty, options),
         syn::Data::Struct(syn::DataStruct {
             fields: syn::Fields::Unit,
             ..
@@ -206,7 +483,7 @@ fn derive_struct(ty: syn::DeriveInput) -> TokenStream {
     }
 }
 
-fn derive_named(ty: syn::DeriveInput) -> TokenStream {
+fn derive_named(ty: syn::DeriveInput, options: Options) -> TokenStream {
     let syn::DeriveInput {
         vis,
         ident: input_ident,
@@ -228,7 +505,7 @@ fn derive_named(ty: syn::DeriveInput) -> TokenStream {
     let module_name = input_ident.append("_fields");
 
     let mut module = new_module(module_name.clone());
-    module.vis = vis;
+    module.vis = vis.clone();
 
     let contents = &mut module.content.as_mut().unwrap().1;
 
@@ -241,52 +518,68 @@ fn derive_named(ty: syn::DeriveInput) -> TokenStream {
         use super::*;
     ));
 
+    let mut field_types = Vec::new();
+    let mut members = Vec::new();
+    let mut field_options = Vec::new();
+
     let (generic_header, generic, where_clause) = generics.split_for_impl();
+    let inner_generics = prefixed(&input_ident, &generics, quote!(super::));
+    let (inner_header, _, inner_where) = inner_generics.split_for_impl();
+    let field_list = fields.named.iter().cloned().collect::<Vec<_>>();
     for field in fields.named {
+        match FieldOptions::parse(&field.attrs) {
+            Ok(options) => field_options.push(options),
+            Err(err) => return err.to_compile_error().into(),
+        }
+
         let ident = field.ident.unwrap();
 
         contents.push(item!(
             #[allow(non_camel_case_types)]
-            pub struct #ident<T>(::gfp_core::derive::Invariant<T>);
+            pub struct #ident<T, P = ::gfp_core::Identity<T>>(::gfp_core::derive::Invariant<(T, P)>);
         ));
 
         contents.push(item!(
-            impl<T> #ident<T> {
+            impl<T, P> #ident<T, P> {
                 pub const INIT: Self = Self(::gfp_core::derive::Invariant::INIT);
             }
         ));
 
         contents.push(item!(
-            impl<T> Clone for #ident<T> {
+            impl<T, P> Clone for #ident<T, P> {
                 fn clone(&self) -> Self { *self }
             }
         ));
 
         contents.push(item!(
-            impl<T> Copy for #ident<T> {}
+            impl<T, P> Copy for #ident<T, P> {}
         ));
 
         let ty = &field.ty;
+        field_types.push(ty.clone());
+        members.push(syn::Member::Named(ident.clone()));
 
         contents.push(item!(
-            unsafe impl #generic_header ::gfp_core::Field for #ident<super::#input_ident #generic> {
-                type Parent = super::#input_ident #generic;
+            unsafe impl #inner_header ::gfp_core::Field for #ident<super::#input_ident #generic, __Prefix> #inner_where {
+                type Parent = <__Prefix as ::gfp_core::Field>::Parent;
                 type Type = #ty;
 
                 #[inline]
                 unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+                    let ptr = <__Prefix as ::gfp_core::derive::Prefix>::INIT.project_raw(ptr);
                     ::gfp_core::ptr_project!(const ptr #ident)
                 }
 
                 #[inline]
                 unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+                    let ptr = <__Prefix as ::gfp_core::derive::Prefix>::INIT.project_raw_mut(ptr);
                     ::gfp_core::ptr_project!(mut ptr #ident)
                 }
             }
         ));
 
         let ty = TokenStream::from(quote!(
-            #module_name::#ident<#input_ident #generic>
+            #module_name::#ident<#input_ident #generic, __Prefix>
         ));
         let ty = syn::parse_macro_input!(ty as syn::Type);
 
@@ -306,9 +599,67 @@ fn derive_named(ty: syn::DeriveInput) -> TokenStream {
     }
 
     let field_type_name = input_ident.append("Fields");
-
+    let (fields_generics, nested) = derive_nested(
+        &input_ident,
+        &generics,
+        &module_name,
+        &field_type_name,
+        &members,
+        &field_types,
+        quote!(#field_type_name { #fields_new }),
+    );
+    let has_fields = derive_has_fields(
+        &input_ident,
+        &generics,
+        &fields_generics,
+        quote!(#field_type_name { #fields_new }),
+        &members,
+        &field_types,
+        Kind::Struct,
+    );
+
+    let markers =
+        options.derive_markers(&input_ident, &generics, &field_types);
+    let layout = match options.derive_layout(
+        &input_ident,
+        &generics,
+        &members,
+        &field_types,
+        &field_options,
+    ) {
+        Ok(layout) => layout,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let views = match options.derive_views(
+        &vis,
+        &input_ident,
+        &generics,
+        &field_list,
+        &members,
+        &field_options,
+    ) {
+        Ok(views) => views,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let copy_named = options.derive_copy_named(
+        &input_ident,
+        &generics,
+        &members,
+        &field_types,
+        &field_options,
+    );
+    let reflect = derive_reflect(
+        &input_ident,
+        &generics,
+        &members,
+        &field_types,
+        Kind::Struct,
+    );
+
+    // the fields struct is the `Fields` of `Nested` and `HasFields`, so it
+    // must be as visible as the type to avoid a private type in their impls
     TokenStream::from(quote! {
-        struct #field_type_name #generic_header #where_clause {
+        #vis struct #field_type_name #fields_generics #where_clause {
             #fields_marker
         }
 
@@ -326,10 +677,24 @@ fn derive_named(ty: syn::DeriveInput) -> TokenStream {
 
         #[allow(non_snake_case)]
         #module
+
+        #nested
+
+        #has_fields
+
+        #markers
+
+        #views
+
+        #copy_named
+
+        #reflect
+
+        #layout
     })
 }
 
-fn derive_unnamed(ty: syn::DeriveInput) -> TokenStream {
+fn derive_unnamed(ty: syn::DeriveInput, options: Options) -> TokenStream {
     let syn::DeriveInput {
         vis,
         ident: input_ident,
@@ -351,7 +716,7 @@ fn derive_unnamed(ty: syn::DeriveInput) -> TokenStream {
     let module_name = input_ident.append("_fields");
 
     let mut module = new_module(module_name.clone());
-    module.vis = vis;
+    module.vis = vis.clone();
 
     let contents = &mut module.content.as_mut().unwrap().1;
 
@@ -364,58 +729,74 @@ fn derive_unnamed(ty: syn::DeriveInput) -> TokenStream {
         use super::*;
     ));
 
+    let mut field_types = Vec::new();
+    let mut members = Vec::new();
+    let mut field_options = Vec::new();
+
     let (generic_header, generic, where_clause) = generics.split_for_impl();
+    let inner_generics = prefixed(&input_ident, &generics, quote!(super::));
+    let (inner_header, _, inner_where) = inner_generics.split_for_impl();
+    let field_list = fields.unnamed.iter().cloned().collect::<Vec<_>>();
     for (i, field) in fields.unnamed.iter().enumerate() {
+        match FieldOptions::parse(&field.attrs) {
+            Ok(options) => field_options.push(options),
+            Err(err) => return err.to_compile_error().into(),
+        }
+
         use syn::spanned::Spanned;
         let ident = quote::format_ident!("_{}", i, span = field.span());
 
         contents.push(item!(
             #[allow(non_camel_case_types)]
-            pub struct #ident<T>(::gfp_core::derive::Invariant<T>);
+            pub struct #ident<T, P = ::gfp_core::Identity<T>>(::gfp_core::derive::Invariant<(T, P)>);
         ));
 
         contents.push(item!(
-            impl<T> #ident<T> {
+            impl<T, P> #ident<T, P> {
                 pub const INIT: Self = Self(::gfp_core::derive::Invariant::INIT);
             }
         ));
 
         contents.push(item!(
-            impl<T> Clone for #ident<T> {
+            impl<T, P> Clone for #ident<T, P> {
                 fn clone(&self) -> Self { *self }
             }
         ));
 
         contents.push(item!(
-            impl<T> Copy for #ident<T> {}
+            impl<T, P> Copy for #ident<T, P> {}
         ));
 
         let ty = &field.ty;
+        field_types.push(ty.clone());
 
         let index = syn::Member::Unnamed(syn::Index {
             index: i as u32,
             span:  proc_macro2::Span::call_site(),
         });
+        members.push(index.clone());
 
         contents.push(item!(
-            unsafe impl #generic_header ::gfp_core::Field for #ident<super::#input_ident #generic> {
-                type Parent = super::#input_ident #generic;
+            unsafe impl #inner_header ::gfp_core::Field for #ident<super::#input_ident #generic, __Prefix> #inner_where {
+                type Parent = <__Prefix as ::gfp_core::Field>::Parent;
                 type Type = #ty;
 
                 #[inline]
                 unsafe fn project_raw(&self, ptr: *const Self::Parent) -> *const Self::Type {
+                    let ptr = <__Prefix as ::gfp_core::derive::Prefix>::INIT.project_raw(ptr);
                     &(*ptr).#index
                 }
 
                 #[inline]
                 unsafe fn project_raw_mut(&self, ptr: *mut Self::Parent) -> *mut Self::Type {
+                    let ptr = <__Prefix as ::gfp_core::derive::Prefix>::INIT.project_raw_mut(ptr);
                     &mut (*ptr).#index
                 }
             }
         ));
 
         let ty = TokenStream::from(quote!(
-            #module_name::#ident<#input_ident #generic>
+            #module_name::#ident<#input_ident #generic, __Prefix>
         ));
         let ty = syn::parse_macro_input!(ty as syn::Type);
 
@@ -433,9 +814,67 @@ fn derive_unnamed(ty: syn::DeriveInput) -> TokenStream {
     }
 
     let field_type_name = input_ident.append("Fields");
-
+    let (fields_generics, nested) = derive_nested(
+        &input_ident,
+        &generics,
+        &module_name,
+        &field_type_name,
+        &members,
+        &field_types,
+        quote!(#field_type_name(#fields_new)),
+    );
+    let has_fields = derive_has_fields(
+        &input_ident,
+        &generics,
+        &fields_generics,
+        quote!(#field_type_name(#fields_new)),
+        &members,
+        &field_types,
+        Kind::Struct,
+    );
+
+    let markers =
+        options.derive_markers(&input_ident, &generics, &field_types);
+    let layout = match options.derive_layout(
+        &input_ident,
+        &generics,
+        &members,
+        &field_types,
+        &field_options,
+    ) {
+        Ok(layout) => layout,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let views = match options.derive_views(
+        &vis,
+        &input_ident,
+        &generics,
+        &field_list,
+        &members,
+        &field_options,
+    ) {
+        Ok(views) => views,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let copy_named = options.derive_copy_named(
+        &input_ident,
+        &generics,
+        &members,
+        &field_types,
+        &field_options,
+    );
+    let reflect = derive_reflect(
+        &input_ident,
+        &generics,
+        &members,
+        &field_types,
+        Kind::Struct,
+    );
+
+    // the fields struct is the `Fields` of `Nested` and `HasFields`, so it
+    // must be as visible as the type to avoid a private type in their impls
     TokenStream::from(quote! {
-        struct #field_type_name #generic_header(#fields_marker) #where_clause;
+        #vis struct #field_type_name #fields_generics(#fields_marker) #where_clause;
 
         impl#generic_header #input_ident #generic #where_clause {
             const FIELDS: #field_type_name #generic = #field_type_name(#fields_new);
@@ -447,10 +886,60 @@ fn derive_unnamed(ty: syn::DeriveInput) -> TokenStream {
 
         #[allow(non_snake_case)]
         #module
+
+        #nested
+
+        #has_fields
+
+        #markers
+
+        #views
+
+        #copy_named
+
+        #reflect
+
+        #layout
     })
 }
 
-fn derive_union(ty: syn::DeriveInput) -> TokenStream {
+fn derive_union(ty: syn::DeriveInput, options: Options) -> TokenStream {
+    if options.as_bytes {
+        return syn::Error::new(
+            ty.ident.span(),
+            "`as_bytes` is not supported for unions",
+        )
+        .to_compile_error()
+        .into()
+    }
+
+    if options.no_padding {
+        return syn::Error::new(
+            ty.ident.span(),
+            "`no_padding` is not supported for unions",
+        )
+        .to_compile_error()
+        .into()
+    }
+
+    if options.views || !options.subsets.is_empty() {
+        return syn::Error::new(
+            ty.ident.span(),
+            "views are not supported for unions",
+        )
+        .to_compile_error()
+        .into()
+    }
+
+    if !options.copy_from.is_empty() {
+        return syn::Error::new(
+            ty.ident.span(),
+            "`copy_from` is not supported for unions",
+        )
+        .to_compile_error()
+        .into()
+    }
+
     let syn::DeriveInput {
         vis,
         ident: input_ident,
@@ -471,7 +960,7 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
     let module_name = input_ident.append("_fields");
 
     let mut module = new_module(module_name.clone());
-    module.vis = vis;
+    module.vis = vis.clone();
 
     let contents = &mut module.content.as_mut().unwrap().1;
 
@@ -484,8 +973,27 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
         use super::*;
     ));
 
+    let mut field_types = Vec::new();
+    let mut members = Vec::new();
+    let mut field_options = Vec::new();
+
     let (generic_header, generic, where_clause) = generics.split_for_impl();
     for field in fields.named {
+        match FieldOptions::parse(&field.attrs) {
+            Ok(options) if options.pin || options.skip_copy => {
+                let option = if options.pin { "pin" } else { "skip_copy" };
+
+                return syn::Error::new(
+                    field.ident.as_ref().unwrap().span(),
+                    format!("`{}` is not supported for unions", option),
+                )
+                .to_compile_error()
+                .into()
+            },
+            Ok(options) => field_options.push(options),
+            Err(err) => return err.to_compile_error().into(),
+        }
+
         let ident = field.ident.unwrap();
 
         contents.push(item!(
@@ -512,6 +1020,8 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
         ));
 
         let ty = &field.ty;
+        field_types.push(ty.clone());
+        members.push(syn::Member::Named(ident.clone()));
 
         contents.push(item!(
             unsafe impl #generic_header ::gfp_core::Field for #ident<super::#input_ident #generic> {
@@ -551,9 +1061,40 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
     }
 
     let field_type_name = input_ident.append("Fields");
-
+    let has_fields = derive_has_fields(
+        &input_ident,
+        &generics,
+        &generics,
+        quote!(#field_type_name { #fields_new }),
+        &members,
+        &field_types,
+        Kind::Union,
+    );
+
+    let markers =
+        options.derive_markers(&input_ident, &generics, &field_types);
+    let layout = match options.derive_layout(
+        &input_ident,
+        &generics,
+        &members,
+        &field_types,
+        &field_options,
+    ) {
+        Ok(layout) => layout,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let reflect = derive_reflect(
+        &input_ident,
+        &generics,
+        &members,
+        &field_types,
+        Kind::Union,
+    );
+
+    // the fields struct is the `Fields` of `HasUnionFields`, so it must be as
+    // visible as the type to avoid a private type in its impl
     TokenStream::from(quote! {
-        struct #field_type_name #generic_header #where_clause {
+        #vis struct #field_type_name #generic_header #where_clause {
             #fields_marker
         }
 
@@ -567,9 +1108,1020 @@ fn derive_union(ty: syn::DeriveInput) -> TokenStream {
 
         #[allow(non_snake_case)]
         #module
+
+        #has_fields
+
+        #markers
+
+        #reflect
+
+        #layout
     })
 }
 
+/// The options given to the derive with `#[field(...)]` on the type
+#[derive(Default)]
+struct Options {
+    /// `#[field(pod)]`, implement `gfp_core::Pod`
+    pod:        bool,
+    /// `#[field(as_bytes)]`, implement `gfp_core::AsBytes`
+    as_bytes:   bool,
+    /// `#[field(no_padding)]`, assert that the type has no padding
+    no_padding: bool,
+    /// `#[field(views)]`, generate views of every field
+    views:      bool,
+    /// `#[field(view(Name(fields...)))]`, generate views of some fields
+    subsets:    Vec<View>,
+    /// `#[field(copy_from(Types...))]`, copy fields by name from the types
+    copy_from:  Vec<syn::Path>,
+}
+
+/// A view of some of the fields, declared with `#[field(view(Name(...)))]`
+struct View {
+    name:    syn::Ident,
+    members: Vec<syn::Member>,
+}
+
+impl View {
+    fn parse(nested: &syn::NestedMeta) -> syn::Result<Self> {
+        let list = match nested {
+            syn::NestedMeta::Meta(syn::Meta::List(list)) => list,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    nested,
+                    "expected `Name(fields...)`",
+                ))
+            },
+        };
+
+        let name = match list.path.get_ident() {
+            Some(name) => name.clone(),
+            None => {
+                return Err(syn::Error::new_spanned(
+                    &list.path,
+                    "expected the name of the view",
+                ))
+            },
+        };
+
+        let mut members = Vec::new();
+
+        for field in &list.nested {
+            let member = match field {
+                syn::NestedMeta::Meta(syn::Meta::Path(path))
+                    if path.get_ident().is_some() =>
+                {
+                    syn::Member::Named(path.get_ident().unwrap().clone())
+                },
+                syn::NestedMeta::Lit(syn::Lit::Int(index)) => {
+                    syn::Member::Unnamed(syn::Index {
+                        index: index.base10_parse()?,
+                        span:  index.span(),
+                    })
+                },
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "expected a field name or index",
+                    ))
+                },
+            };
+
+            if members.contains(&member) {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "field is already in the view",
+                ))
+            }
+
+            members.push(member);
+        }
+
+        Ok(Self {
+            name,
+            members,
+        })
+    }
+}
+
+impl Options {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        for attr in attrs {
+            if !attr.path.is_ident("field") {
+                continue
+            }
+
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(list) => list,
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "expected `#[field(...)]`",
+                    ))
+                },
+            };
+
+            for nested in list.nested {
+                let flag = match nested {
+                    syn::NestedMeta::Meta(syn::Meta::List(ref list))
+                        if list.path.is_ident("view") =>
+                    {
+                        for view in &list.nested {
+                            options.subsets.push(View::parse(view)?);
+                        }
+
+                        continue
+                    },
+                    syn::NestedMeta::Meta(syn::Meta::List(ref list))
+                        if list.path.is_ident("copy_from") =>
+                    {
+                        for source in &list.nested {
+                            let path = match source {
+                                syn::NestedMeta::Meta(syn::Meta::Path(
+                                    path,
+                                )) => path,
+                                _ => {
+                                    return Err(syn::Error::new_spanned(
+                                        source,
+                                        "expected a type",
+                                    ))
+                                },
+                            };
+
+                            options.copy_from.push(path.clone());
+                        }
+
+                        continue
+                    },
+                    syn::NestedMeta::Meta(syn::Meta::Path(ref path))
+                        if path.is_ident("pod") =>
+                    {
+                        &mut options.pod
+                    },
+                    syn::NestedMeta::Meta(syn::Meta::Path(ref path))
+                        if path.is_ident("as_bytes") =>
+                    {
+                        &mut options.as_bytes
+                    },
+                    syn::NestedMeta::Meta(syn::Meta::Path(ref path))
+                        if path.is_ident("no_padding") =>
+                    {
+                        &mut options.no_padding
+                    },
+                    syn::NestedMeta::Meta(syn::Meta::Path(ref path))
+                        if path.is_ident("views") =>
+                    {
+                        &mut options.views
+                    },
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            nested,
+                            "unknown `field` option",
+                        ))
+                    },
+                };
+
+                *flag = true;
+            }
+        }
+
+        // a `Pod` can be viewed in place, so its fields must be aligned
+        if options.pod {
+            if let Some(repr) = find_packed(attrs) {
+                return Err(syn::Error::new_spanned(
+                    repr,
+                    "`pod` is not supported for `repr(packed)` types",
+                ))
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn derive_markers(
+        &self,
+        ident: &syn::Ident,
+        generics: &syn::Generics,
+        field_types: &[syn::Type],
+    ) -> proc_macro2::TokenStream {
+        let mut output = proc_macro2::TokenStream::new();
+
+        if self.pod {
+            let pod = syn::parse_quote!(::gfp_core::Pod);
+            let (generic_header, generic, where_clause) =
+                &bound_fields(generics, field_types, pod);
+
+            output.extend(quote! {
+                unsafe impl #generic_header ::gfp_core::Pod for #ident #generic #where_clause {}
+            });
+        }
+
+        if self.as_bytes {
+            let as_bytes = syn::parse_quote!(::gfp_core::AsBytes);
+            let (generic_header, generic, where_clause) =
+                &bound_fields(generics, field_types, as_bytes);
+
+            let message =
+                format!("`{}` must not contain any padding bytes", ident);
+
+            output.extend(quote! {
+                unsafe impl #generic_header ::gfp_core::AsBytes for #ident #generic #where_clause {
+                    // the fields may have padding of their own, which isn't
+                    // visible from their size
+                    const NO_PADDING: () = {
+                        #(let () = <#field_types as ::gfp_core::AsBytes>::NO_PADDING;)*
+                        assert!(
+                            ::core::mem::size_of::<Self>() == 0 #(+ ::core::mem::size_of::<#field_types>())*,
+                            #message
+                        )
+                    };
+                }
+            });
+        }
+
+        output
+    }
+
+    /// Implement `CopyNamed` for every type in `#[field(copy_from(...))]`,
+    /// which copies every field that isn't `#[field(skip_copy)]`
+    fn derive_copy_named(
+        &self,
+        ident: &syn::Ident,
+        generics: &syn::Generics,
+        members: &[syn::Member],
+        field_types: &[syn::Type],
+        field_options: &[FieldOptions],
+    ) -> proc_macro2::TokenStream {
+        let (members, field_types): (Vec<_>, Vec<_>) = members
+            .iter()
+            .zip(field_types)
+            .zip(field_options)
+            .filter(|(_, options)| !options.skip_copy)
+            .map(|(field, _)| field)
+            .unzip();
+        let names = members
+            .iter()
+            .map(|member| field_name_type(&member_name(member)))
+            .collect::<Vec<_>>();
+
+        let mut output = proc_macro2::TokenStream::new();
+
+        for source in &self.copy_from {
+            let mut generics = generics.clone();
+            let where_clause = generics.make_where_clause();
+
+            // a field that `source` doesn't have fails to compile here
+            for (ty, name) in field_types.iter().zip(&names) {
+                where_clause.predicates.push(syn::parse_quote!(
+                    #ty: ::core::convert::From<<#source as ::gfp_core::HasField<#name>>::Type>
+                ));
+                where_clause.predicates.push(syn::parse_quote!(
+                    <#source as ::gfp_core::HasField<#name>>::Type: ::core::clone::Clone
+                ));
+            }
+
+            let (generic_header, generic, where_clause) =
+                generics.split_for_impl();
+
+            output.extend(quote! {
+                impl #generic_header ::gfp_core::CopyNamed<#source> for #ident #generic #where_clause {
+                    #[allow(unused_variables)]
+                    fn copy_named(&mut self, src: &#source) {
+                        #(
+                            let field = <#source as ::gfp_core::HasField<#names>>::FIELD;
+                            let value = ::gfp_core::ProjectTo::project_to(src, field);
+                            self.#members = ::core::convert::From::from(
+                                ::core::clone::Clone::clone(value)
+                            );
+                        )*
+                    }
+                }
+            });
+        }
+
+        output
+    }
+
+    /// Generate the views given by `#[field(views)]` and `#[field(view(...))]`
+    /// on the type, and make `#[field(pin)]` fields safe to pin project
+    fn derive_views(
+        &self,
+        vis: &syn::Visibility,
+        ident: &syn::Ident,
+        generics: &syn::Generics,
+        fields: &[syn::Field],
+        members: &[syn::Member],
+        field_options: &[FieldOptions],
+    ) -> syn::Result<proc_macro2::TokenStream> {
+        let mut output = proc_macro2::TokenStream::new();
+        let (generic_header, generic, where_clause) = generics.split_for_impl();
+
+        let pinned = fields
+            .iter()
+            .zip(field_options)
+            .filter(|(_, options)| options.pin)
+            .map(|(field, _)| &field.ty)
+            .collect::<Vec<_>>();
+
+        if !pinned.is_empty() {
+            // Dev Note: pinned fields must never be moved after they are
+            // pinned, so the type may not implement `Drop`, and may only be
+            // `Unpin` if all of the pinned fields are `Unpin`. The lifetime
+            // keeps the bounds from being trivial
+            let mut unpin = generics.clone();
+            unpin.params.insert(0, syn::parse_quote!('__pin));
+            let where_unpin = unpin.make_where_clause();
+
+            for ty in &pinned {
+                where_unpin.predicates.push(syn::parse_quote!(
+                    ::gfp_core::derive::Pinned<'__pin, #ty>: ::core::marker::Unpin
+                ));
+            }
+
+            let (unpin_header, _, unpin_where) = unpin.split_for_impl();
+
+            output.extend(quote! {
+                const _: () = {
+                    trait MustNotImplDrop {}
+
+                    #[allow(drop_bounds)]
+                    impl<T: ::core::ops::Drop> MustNotImplDrop for T {}
+
+                    impl #generic_header MustNotImplDrop for #ident #generic #where_clause {}
+                };
+
+                impl #unpin_header ::core::marker::Unpin for #ident #generic #unpin_where {}
+            });
+        }
+
+        if (self.views || !self.subsets.is_empty()) && fields.is_empty() {
+            return Err(syn::Error::new(
+                ident.span(),
+                "views are not supported for types without fields",
+            ))
+        }
+
+        let mut views = Vec::new();
+
+        if self.views {
+            views.push((ident.clone(), (0..fields.len()).collect::<Vec<_>>()));
+        }
+
+        for view in &self.subsets {
+            let mut indices = Vec::new();
+
+            for member in &view.members {
+                match members.iter().position(|field| field == member) {
+                    Some(index) => indices.push(index),
+                    None => {
+                        return Err(syn::Error::new_spanned(
+                            member,
+                            format!("`{}` has no field `{}`", ident, member_name(member)),
+                        ))
+                    },
+                }
+            }
+
+            views.push((view.name.clone(), indices));
+        }
+
+        let mut view_generics = generics.clone();
+        view_generics.params.insert(0, syn::parse_quote!('__view));
+        let (view_header, view_generic, _) = view_generics.split_for_impl();
+
+        let is_tuple = fields.iter().all(|field| field.ident.is_none());
+
+        for (name, indices) in views {
+            // a view of some of the fields may not use every generic parameter
+            let phantom = if indices.len() < fields.len() && !generics.params.is_empty() {
+                Some(quote!(::core::marker::PhantomData<fn() -> #ident #generic>))
+            } else {
+                None
+            };
+            let phantom = phantom.as_ref();
+
+            let view_ref = name.append("Ref");
+            let view_mut = name.append("Mut");
+            let view_pin = name.append("Pin");
+
+            let field_vis = indices.iter().map(|&i| &fields[i].vis).collect::<Vec<_>>();
+            let view_members = indices.iter().map(|&i| &members[i]).collect::<Vec<_>>();
+            let bindings = view_members
+                .iter()
+                .map(|member| quote::format_ident!("__{}", member_name(member)))
+                .collect::<Vec<_>>();
+            let types = indices.iter().map(|&i| &fields[i].ty).collect::<Vec<_>>();
+            let pin_types = indices.iter().map(|&i| {
+                let ty = &fields[i].ty;
+
+                if field_options[i].pin {
+                    quote!(::core::pin::Pin<&'__view mut #ty>)
+                } else {
+                    quote!(&'__view mut #ty)
+                }
+            });
+            let pin_values = indices.iter().zip(&bindings).map(|(&i, binding)| {
+                if field_options[i].pin {
+                    quote!(::core::pin::Pin::new_unchecked(#binding))
+                } else {
+                    quote!(#binding)
+                }
+            });
+
+            // the views are `allow(dead_code)`, because most types will only
+            // use some of them
+            let declare = |view: &syn::Ident, types: Vec<proc_macro2::TokenStream>| {
+                let phantom = phantom.into_iter();
+
+                if is_tuple {
+                    quote! {
+                        #[allow(dead_code)]
+                        #vis struct #view #view_generics (#(#field_vis #types,)* #(#phantom)*) #where_clause;
+                    }
+                } else {
+                    quote! {
+                        #[allow(dead_code)]
+                        #vis struct #view #view_generics #where_clause {
+                            #(#field_vis #view_members: #types,)*
+                            #(__parent: #phantom)*
+                        }
+                    }
+                }
+            };
+
+            let construct = |values: Vec<proc_macro2::TokenStream>| {
+                let phantom = phantom.map(|_| quote!(::core::marker::PhantomData));
+                let phantom = phantom.into_iter();
+
+                if is_tuple {
+                    quote!(Self(#(#values,)* #(#phantom)*))
+                } else {
+                    quote!(Self { #(#view_members: #values,)* #(__parent: #phantom)* })
+                }
+            };
+
+            let values = bindings.iter().map(|binding| quote!(#binding)).collect::<Vec<_>>();
+
+            output.extend(declare(&view_ref, types.iter().map(|ty| quote!(&'__view #ty)).collect()));
+            output.extend(declare(&view_mut, types.iter().map(|ty| quote!(&'__view mut #ty)).collect()));
+            output.extend(declare(&view_pin, pin_types.collect()));
+
+            let construct_ref = construct(values.clone());
+            let construct_pin = construct(pin_values.collect());
+
+            output.extend(quote! {
+                impl #view_header ::core::convert::From<&'__view #ident #generic> for #view_ref #view_generic #where_clause {
+                    #[inline]
+                    fn from(value: &'__view #ident #generic) -> Self {
+                        let #ident { #(#view_members: #bindings,)* .. } = value;
+                        #construct_ref
+                    }
+                }
+
+                impl #view_header ::core::convert::From<&'__view mut #ident #generic> for #view_mut #view_generic #where_clause {
+                    #[inline]
+                    fn from(value: &'__view mut #ident #generic) -> Self {
+                        let #ident { #(#view_members: #bindings,)* .. } = value;
+                        #construct_ref
+                    }
+                }
+
+                impl #view_header ::core::convert::From<::core::pin::Pin<&'__view mut #ident #generic>> for #view_pin #view_generic #where_clause {
+                    #[inline]
+                    fn from(value: ::core::pin::Pin<&'__view mut #ident #generic>) -> Self {
+                        // Safety
+                        //
+                        // * pinned fields are only ever accessed through a `Pin`
+                        // * the type doesn't implement `Drop`, and is only
+                        //      `Unpin` if every pinned field is `Unpin`
+                        unsafe {
+                            let #ident { #(#view_members: #bindings,)* .. } =
+                                ::core::pin::Pin::get_unchecked_mut(value);
+                            #construct_pin
+                        }
+                    }
+                }
+            });
+        }
+
+        if self.views {
+            let view_ref = ident.append("Ref");
+            let view_mut = ident.append("Mut");
+            let view_pin = ident.append("Pin");
+
+            output.extend(quote! {
+                impl #generic_header #ident #generic #where_clause {
+                    #vis fn split<'__view>(&'__view self) -> #view_ref #view_generic {
+                        ::core::convert::From::from(self)
+                    }
+
+                    #vis fn split_mut<'__view>(&'__view mut self) -> #view_mut #view_generic {
+                        ::core::convert::From::from(self)
+                    }
+
+                    #vis fn split_pin<'__view>(self: ::core::pin::Pin<&'__view mut Self>) -> #view_pin #view_generic {
+                        ::core::convert::From::from(self)
+                    }
+                }
+            });
+        }
+
+        Ok(output)
+    }
+
+    /// Assert the layout given by `#[field(no_padding)]` on the type, and
+    /// `#[field(offset = N, size = M)]` on the fields at compile time
+    fn derive_layout(
+        &self,
+        ident: &syn::Ident,
+        generics: &syn::Generics,
+        members: &[syn::Member],
+        field_types: &[syn::Type],
+        field_options: &[FieldOptions],
+    ) -> syn::Result<proc_macro2::TokenStream> {
+        let mut assertions = Vec::new();
+
+        for ((member, ty), options) in
+            members.iter().zip(field_types).zip(field_options)
+        {
+            let name = member_name(member);
+
+            if let Some(offset) = &options.offset {
+                let message = format!(
+                    "`{}::{}` is not at offset {}",
+                    ident, name, offset
+                );
+
+                assertions.push(quote! {
+                    assert!(
+                        ::gfp_core::const_offset!(#ident => #member) == #offset,
+                        #message
+                    );
+                });
+            }
+
+            if let Some(size) = &options.size {
+                let message =
+                    format!("`{}::{}` is not {} bytes", ident, name, size);
+
+                assertions.push(quote! {
+                    assert!(::core::mem::size_of::<#ty>() == #size, #message);
+                });
+            }
+        }
+
+        // only the padding of this type is checked, the fields don't need to
+        // implement anything so padding inside them can't be found
+        if self.no_padding {
+            let message =
+                format!("`{}` must not contain any padding bytes", ident);
+
+            assertions.push(quote! {
+                assert!(
+                    ::core::mem::size_of::<#ident>() == 0 #(+ ::core::mem::size_of::<#field_types>())*,
+                    #message
+                );
+            });
+        }
+
+        if assertions.is_empty() {
+            return Ok(proc_macro2::TokenStream::new())
+        }
+
+        if !generics.params.is_empty() {
+            return Err(syn::Error::new(
+                ident.span(),
+                "layout assertions are not supported for generic types",
+            ))
+        }
+
+        Ok(quote! {
+            const _: () = {
+                #(#assertions)*
+            };
+        })
+    }
+}
+
+/// Find a `#[repr(packed)]` or `#[repr(packed(N))]` attribute
+fn find_packed(attrs: &[syn::Attribute]) -> Option<&syn::Attribute> {
+    attrs.iter().find(|attr| {
+        if !attr.path.is_ident("repr") {
+            return false
+        }
+
+        match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested.iter().any(|nested| {
+                match nested {
+                    syn::NestedMeta::Meta(meta) => {
+                        meta.path().is_ident("packed")
+                    },
+                    _ => false,
+                }
+            }),
+            _ => false,
+        }
+    })
+}
+
+/// The options given to the derive with `#[field(...)]` on a field
+#[derive(Default)]
+struct FieldOptions {
+    /// `#[field(offset = N)]`, assert that the field is at offset `N`
+    offset:    Option<syn::LitInt>,
+    /// `#[field(size = N)]`, assert that the field is `N` bytes
+    size:      Option<syn::LitInt>,
+    /// `#[field(pin)]`, the field is pinned in the pinned views
+    pin:       bool,
+    /// `#[field(skip_copy)]`, the field is not copied by `copy_from`
+    skip_copy: bool,
+}
+
+impl FieldOptions {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        for attr in attrs {
+            if !attr.path.is_ident("field") {
+                continue
+            }
+
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(list) => list,
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "expected `#[field(...)]`",
+                    ))
+                },
+            };
+
+            for nested in list.nested {
+                let (option, lit) = match nested {
+                    syn::NestedMeta::Meta(syn::Meta::Path(ref path))
+                        if path.is_ident("pin") =>
+                    {
+                        options.pin = true;
+                        continue
+                    },
+                    syn::NestedMeta::Meta(syn::Meta::Path(ref path))
+                        if path.is_ident("skip_copy") =>
+                    {
+                        options.skip_copy = true;
+                        continue
+                    },
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(
+                        syn::MetaNameValue {
+                            ref path,
+                            lit: syn::Lit::Int(ref lit),
+                            ..
+                        },
+                    )) if path.is_ident("offset") => (&mut options.offset, lit),
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(
+                        syn::MetaNameValue {
+                            ref path,
+                            lit: syn::Lit::Int(ref lit),
+                            ..
+                        },
+                    )) if path.is_ident("size") => (&mut options.size, lit),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            nested,
+                            "unknown `field` option",
+                        ))
+                    },
+                };
+
+                *option = Some(lit.clone());
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// The name of a field, tuple fields are named by their index
+fn member_name(member: &syn::Member) -> String {
+    match member {
+        syn::Member::Named(ident) => {
+            ident.to_string().trim_start_matches("r#").to_string()
+        },
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+/// The name of the field type of `member`
+fn marker_ident(member: &syn::Member) -> syn::Ident {
+    match member {
+        syn::Member::Named(ident) => ident.clone(),
+        syn::Member::Unnamed(index) => quote::format_ident!("_{}", index.index),
+    }
+}
+
+/// The kind of type that is being derived
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Struct,
+    Union,
+}
+
+/// Implement `gfp_core::Reflect`, union fields can't be accessed safely, so
+/// they don't get a `TypeId`, nested fields, or visitors
+fn derive_reflect(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    members: &[syn::Member],
+    field_types: &[syn::Type],
+    kind: Kind,
+) -> proc_macro2::TokenStream {
+    let (generic_header, generic, where_clause) = generics.split_for_impl();
+
+    // without generic parameters, every field type must be `'static`
+    let is_static = kind == Kind::Struct && generics.params.is_empty();
+
+    let info = members.iter().zip(field_types).map(|(member, ty)| {
+        let name = member_name(member);
+
+        let (type_id, any) = if is_static {
+            (
+                quote!(Some(::core::any::TypeId::of::<#ty>)),
+                quote!(Some((
+                    ::gfp_core::derive::as_any::<#ty>,
+                    ::gfp_core::derive::as_any_mut::<#ty>,
+                ))),
+            )
+        } else {
+            (quote!(None), quote!(None))
+        };
+
+        let fields = match kind {
+            Kind::Struct => quote!(|| {
+                #[allow(unused_imports)]
+                use ::gfp_core::derive::{ViaReflect as _, ViaNoFields as _};
+
+                (&::gfp_core::derive::ReflectProbe::<#ty>(::core::marker::PhantomData)).fields()
+            }),
+            Kind::Union => quote!(::gfp_core::derive::no_fields),
+        };
+
+        let debug = match kind {
+            Kind::Struct => quote!(Some(
+                |ptr: *const u8, visitor: &mut dyn FnMut(&dyn ::core::fmt::Debug)| {
+                    #[allow(unused_imports)]
+                    use ::gfp_core::derive::{ViaDebug as _, ViaNothing as _};
+
+                    (&::gfp_core::derive::DebugProbe(ptr.cast::<#ty>())).visit_debug(visitor)
+                }
+            )),
+            Kind::Union => quote!(None),
+        };
+
+        quote! {
+            ::gfp_core::FieldInfo::new(
+                #name,
+                ::gfp_core::const_offset!(Self => #member),
+                ::core::mem::size_of::<#ty>(),
+                ::core::mem::align_of::<#ty>(),
+                ::core::any::type_name::<#ty>,
+                #type_id,
+                #fields,
+                #any,
+                #debug,
+            )
+        }
+    });
+
+    quote! {
+        unsafe impl #generic_header ::gfp_core::Reflect for #ident #generic #where_clause {
+            const FIELD_INFO: &'static [::gfp_core::FieldInfo] = unsafe { &[#(#info),*] };
+        }
+    }
+}
+
+/// Add the `__Prefix` parameter of nested field types to `generics`, `path` is
+/// the path to the module that contains the type
+fn prefixed(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    path: proc_macro2::TokenStream,
+) -> syn::Generics {
+    let mut prefixed = generics.clone();
+    let (_, generic, _) = generics.split_for_impl();
+
+    prefixed.params.push(syn::parse_quote!(__Prefix));
+    prefixed.make_where_clause().predicates.push(syn::parse_quote!(
+        __Prefix: ::gfp_core::derive::Prefix<Type = #path #ident #generic>
+    ));
+
+    prefixed
+}
+
+/// The type-level name of a field, a tuple of `gfp_core::derive::Char`s
+fn field_name_type(name: &str) -> proc_macro2::TokenStream {
+    let chars = name.chars();
+
+    quote!((#(::gfp_core::derive::Char<#chars>,)*))
+}
+
+/// Implement the traits for nested fields, this lets the field types `Deref`
+/// to the field types of their own type, and lets `field_path!` find fields by
+/// name
+///
+/// Returns the generic parameters of the `Fields` struct, and the impls
+fn derive_nested(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    module_name: &syn::Ident,
+    field_type_name: &syn::Ident,
+    members: &[syn::Member],
+    field_types: &[syn::Type],
+    fields: proc_macro2::TokenStream,
+) -> (syn::Generics, proc_macro2::TokenStream) {
+    // without any fields, the `__Prefix` parameter would be unused
+    if members.is_empty() {
+        return (generics.clone(), quote!())
+    }
+
+    let (_, generic, _) = generics.split_for_impl();
+
+    let mut fields_generics = generics.clone();
+    fields_generics.params.push(syn::parse_quote!(
+        __Prefix = ::gfp_core::Identity<#ident #generic>
+    ));
+
+    let nested = prefixed(ident, generics, quote!());
+    let (header, nested_generic, where_clause) = nested.split_for_impl();
+
+    let mut deref = nested.clone();
+    deref.make_where_clause().predicates.push(syn::parse_quote!(
+        <Self as ::gfp_core::Field>::Type: ::gfp_core::derive::Nested<Self>
+    ));
+    let deref_where = &deref.where_clause;
+
+    let items = members.iter().zip(field_types).map(|(member, ty)| {
+        let name = field_name_type(&member_name(member));
+        let field = marker_ident(member);
+        let field = quote!(#module_name::#field<#ident #generic, __Prefix>);
+
+        // Dev Note: if the type of the field is a type parameter, the `Deref`
+        // impl makes method calls ambiguous until the parameter is inferred,
+        // so those fields don't get one
+        let deref = if is_type_param(ty, generics) {
+            quote!()
+        } else {
+            quote! {
+                impl #header ::core::ops::Deref for #field #deref_where {
+                    type Target = <<Self as ::gfp_core::Field>::Type as ::gfp_core::derive::Nested<Self>>::Fields;
+
+                    #[inline]
+                    fn deref(&self) -> &Self::Target {
+                        ::gfp_core::derive::nested::<<Self as ::gfp_core::Field>::Type, Self>(self)
+                    }
+                }
+            }
+        };
+
+        quote! {
+            impl #header ::gfp_core::derive::Prefix for #field #where_clause {
+                const INIT: Self = Self::INIT;
+            }
+
+            impl #header ::gfp_core::derive::Child<#name, __Prefix> for #ident #generic #where_clause {
+                type Field = #field;
+            }
+
+            #deref
+        }
+    });
+
+    let nested = quote! {
+        unsafe impl #header ::gfp_core::derive::Nested<__Prefix> for #ident #generic #where_clause {
+            type Fields = #field_type_name #nested_generic;
+
+            const FIELDS: Self::Fields = #fields;
+        }
+
+        #(#items)*
+    };
+
+    (fields_generics, nested)
+}
+
+/// Implement `gfp_core::HasFields` for structs, or `gfp_core::HasUnionFields`
+/// for unions, and `Copy` for the `Fields` struct
+fn derive_has_fields(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    fields_generics: &syn::Generics,
+    fields: proc_macro2::TokenStream,
+    members: &[syn::Member],
+    field_types: &[syn::Type],
+    kind: Kind,
+) -> proc_macro2::TokenStream {
+    let field_type_name = ident.append("Fields");
+    let module_name = ident.append("_fields");
+    let (generic_header, generic, where_clause) = generics.split_for_impl();
+    let (fields_header, fields_generic, _) = fields_generics.split_for_impl();
+    let count = members.len();
+
+    let has_fields = match kind {
+        Kind::Struct => {
+            let field_at = members.iter().zip(field_types).enumerate();
+            let field_at = field_at.map(|(index, (member, ty))| {
+                let field = marker_ident(member);
+                let field = quote!(#module_name::#field<#ident #generic>);
+
+                quote! {
+                    impl #generic_header ::gfp_core::FieldAt<#index> for #ident #generic #where_clause {
+                        type Type = #ty;
+                        type Field = #field;
+
+                        const FIELD: Self::Field = <#field>::INIT;
+                    }
+                }
+            });
+
+            quote! {
+                impl #generic_header ::gfp_core::HasFields for #ident #generic #where_clause {
+                    type Fields = #field_type_name #generic;
+
+                    const FIELDS: Self::Fields = #fields;
+                    const FIELD_COUNT: usize = #count;
+                }
+
+                #(#field_at)*
+            }
+        },
+        Kind::Union => quote! {
+            impl #generic_header ::gfp_core::HasUnionFields for #ident #generic #where_clause {
+                type Fields = #field_type_name #generic;
+
+                const FIELD_COUNT: usize = #count;
+
+                #[inline]
+                unsafe fn fields() -> Self::Fields {
+                    #fields
+                }
+            }
+        },
+    };
+
+    quote! {
+        impl #fields_header Clone for #field_type_name #fields_generic #where_clause {
+            fn clone(&self) -> Self { *self }
+        }
+
+        impl #fields_header Copy for #field_type_name #fields_generic #where_clause {}
+
+        #has_fields
+    }
+}
+
+/// Check if `ty` is one of the type parameters, or an associated type of one
+fn is_type_param(ty: &syn::Type, generics: &syn::Generics) -> bool {
+    match ty {
+        syn::Type::Path(path) => {
+            path.qself.is_some()
+                || generics.type_params().any(|param| {
+                    path.path.segments.first().map(|segment| &segment.ident)
+                        == Some(&param.ident)
+                })
+        },
+        syn::Type::Group(group) => is_type_param(&group.elem, generics),
+        syn::Type::Paren(paren) => is_type_param(&paren.elem, generics),
+        _ => false,
+    }
+}
+
+/// Add the `bound` to every field type, this rejects any fields that don't
+/// implement `bound` at compile time
+fn bound_fields(
+    generics: &syn::Generics,
+    field_types: &[syn::Type],
+    bound: syn::Path,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    let mut generics = generics.clone();
+    let where_clause = generics.make_where_clause();
+
+    for ty in field_types {
+        where_clause.predicates.push(syn::parse_quote!(#ty: #bound));
+    }
+
+    let (generic_header, generic, where_clause) = generics.split_for_impl();
+
+    (
+        quote!(#generic_header),
+        quote!(#generic),
+        quote!(#where_clause),
+    )
+}
+
 fn new_module(ident: syn::Ident) -> syn::ItemMod {
     syn::ItemMod {
         attrs: Vec::new(),
@@ -0,0 +1,38 @@
+#![feature(raw_ref_op)]
+#![allow(unused)]
+
+use gfp_core::*;
+
+#[derive(Field)]
+#[field(no_padding)]
+#[repr(C)]
+struct Header {
+    #[field(offset = 0, size = 2)]
+    tag:   u16,
+    #[field(offset = 2)]
+    #[field(size = 2)]
+    len:   u16,
+    #[field(offset = 4, size = 4)]
+    crc:   u32,
+    #[field(offset = 8, size = 8)]
+    items: [u16; 4],
+}
+
+#[derive(Field)]
+#[repr(C)]
+struct Padded(#[field(offset = 0)] u8, #[field(offset = 4, size = 4)] u32);
+
+#[derive(Field)]
+#[repr(C)]
+union Word {
+    #[field(offset = 0, size = 4)]
+    int:   u32,
+    #[field(size = 4)]
+    bytes: [u8; 4],
+}
+
+#[test]
+fn layout() {
+    assert_eq!(Header::fields().crc.field_offset(), 4);
+    assert_eq!(Padded::fields().1.field_offset(), 4);
+}